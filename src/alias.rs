@@ -0,0 +1,175 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// A table for Vose's alias method, giving O(1) draws among weighted outcomes.
+///
+/// Each column holds the part, out of the total, of keeping its own outcome,
+/// and the outcome to return otherwise. With integer weights, all computations
+/// are exact.
+#[derive(Debug, Clone)]
+pub(crate) struct AliasTable<S> {
+    total: S,
+    keep: Vec<S>,
+    alias: Vec<usize>,
+}
+
+impl<S: Total> AliasTable<S> {
+    /// Build the table from the weights of the outcomes.
+    ///
+    /// The weights must be non negative and their sum, `total`, must be positive.
+    /// Return None if the scaled weights would overflow.
+    pub fn new(weights: &[S], total: S) -> Option<Self> {
+        let n = weights.len();
+        // a large column, worth at most n times the total, must stay
+        // computable with a small column added
+        total.checked_mul_count(n + 1)?;
+        let mut keep = vec![total; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+        // the weights are scaled so that a full column is worth the total
+        let mut scaled: Vec<S> = weights
+            .iter()
+            .map(|w| w.checked_mul_count(n))
+            .collect::<Option<_>>()?;
+        let mut small = Vec::new();
+        let mut large = Vec::new();
+        for (i, &s) in scaled.iter().enumerate() {
+            if s < total {
+                small.push(i);
+            } else {
+                large.push(i);
+            }
+        }
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            keep[s] = scaled[s];
+            alias[s] = l;
+            scaled[l] = (scaled[l] + scaled[s]).sub_or_zero(total);
+            if scaled[l] < total {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // remaining columns are full, up to rounding errors, and keep
+        // their own outcome (keep and alias are already initialized so)
+        Some(Self { total, keep, alias })
+    }
+    /// Draw the index of an outcome
+    pub fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let column = rng.random_range(0..self.keep.len());
+        if self.total.random_below(rng) < self.keep[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+#[cfg(test)]
+impl<S: Total> AliasTable<S> {
+    /// Compute back, from the columns, the weights of the outcomes multiplied
+    /// by the number of columns
+    fn implied_weights(&self) -> Vec<S> {
+        let mut weights = vec![S::ZERO; self.keep.len()];
+        for (column, (&keep, &alias)) in self.keep.iter().zip(&self.alias).enumerate() {
+            weights[column] = weights[column] + keep;
+            weights[alias] = weights[alias] + self.total.sub_or_zero(keep);
+        }
+        weights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        rand::SeedableRng,
+        rand_chacha::ChaCha8Rng,
+    };
+
+    fn choices<W: Weight>(weights: &[W]) -> Vec<Choice<usize, W>> {
+        weights
+            .iter()
+            .enumerate()
+            .map(|(value, &weight)| Choice { weight, value })
+            .collect()
+    }
+
+    fn alias_table<W: Weight>(
+        choices: &[Choice<usize, W>],
+        total: W::Total,
+    ) -> AliasTable<W::Total> {
+        match Index::new(Strategy::Alias, choices, total) {
+            Index::Alias(table) => table,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn integer_weights_are_exactly_reproduced() {
+        let weights = [1u64, 2, 3, 0, 4];
+        let table = AliasTable::new(&weights, 10).unwrap();
+        let expected: Vec<u64> = weights.iter().map(|w| w * 5).collect();
+        assert_eq!(table.implied_weights(), expected);
+    }
+
+    #[test]
+    fn none_gets_the_remaining_weight() {
+        let table = alias_table(&choices(&[1u32, 2, 3]), 10);
+        // outcomes are the choices then None, 4 columns
+        assert_eq!(table.implied_weights(), vec![4, 8, 12, 16]);
+    }
+
+    #[test]
+    fn truncated_total_keeps_only_reachable_weights() {
+        // the third choice is cut to 1, the fourth one is unreachable
+        let table = alias_table(&choices(&[2u32, 2, 3, 5]), 5);
+        assert_eq!(table.implied_weights(), vec![10, 10, 5, 0, 0]);
+    }
+
+    #[test]
+    fn float_weights_are_reproduced_despite_leftover_columns() {
+        let weights = [0.1, 0.2, 0.3, 0.7, 1e-9, 0.15];
+        let total = weights.iter().sum();
+        let table = AliasTable::new(&weights, total).unwrap();
+        for (implied, weight) in table.implied_weights().iter().zip(weights) {
+            assert!((implied - weight * 6.0).abs() < 1e-12, "{implied} vs {weight}");
+        }
+        let table = alias_table(&choices(&[0.1f64, 0.2, 0.3]), 0.5);
+        let reachable = [0.1, 0.2, 0.2, 0.0];
+        for (implied, weight) in table.implied_weights().iter().zip(reachable) {
+            assert!((implied - weight * 4.0).abs() < 1e-12, "{implied} vs {weight}");
+        }
+    }
+
+    #[test]
+    fn overflowing_scaled_weights_fall_back_to_cumulative() {
+        assert!(AliasTable::new(&[1u64, u64::MAX - 1], u64::MAX).is_none());
+        let selector = RandomSelector::default()
+            .with(1u32, 'A')
+            .with(1, 'B')
+            .with(1, 'C')
+            .with_none(u64::MAX / 2)
+            .with_strategy(Strategy::Alias);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        assert!((0..1000).all(|_| selector.select_with_rng(&mut rng).is_none()));
+        let index = Index::new(Strategy::Alias, &selector.choices, selector.total_weight());
+        assert!(matches!(index, Index::Cumulative(_)));
+    }
+
+    #[test]
+    fn seeded_draws_follow_the_weights() {
+        let table = alias_table(&choices(&[1u32, 2, 3]), 8);
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        let mut counts = [0usize; 4];
+        let draws = 80_000;
+        for _ in 0..draws {
+            counts[table.draw(&mut rng)] += 1;
+        }
+        for (count, weight) in counts.iter().zip([1.0, 2.0, 3.0, 2.0]) {
+            let expected = draws as f64 * weight / 8.0;
+            assert!((*count as f64 - expected).abs() < expected * 0.03, "{counts:?}");
+        }
+    }
+}
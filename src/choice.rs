@@ -0,0 +1,6 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct Choice<T, W> {
+    pub weight: W,
+    pub value: T,
+}
@@ -0,0 +1,150 @@
+use {
+    crate::*,
+    std::{
+        collections::HashMap,
+        hash::BuildHasher,
+    },
+};
+
+/// Build a selector from `(weight, value)` pairs, each one being added
+/// with [RandomSelector::with].
+///
+/// The weights aren't checked: use [RandomSelector::try_from_iter] if they
+/// may be negative, NaN or infinite.
+///
+/// ```
+/// use rand_select::RandomSelector;
+/// let selector: RandomSelector<char> = [(1.0, 'A'), (2.0, 'B')]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(selector.total_weight(), 3.0);
+/// ```
+impl<T, W: Weight> FromIterator<(W, T)> for RandomSelector<T, W> {
+    fn from_iter<I: IntoIterator<Item = (W, T)>>(iter: I) -> Self {
+        let mut selector = Self::default();
+        selector.extend(iter);
+        selector
+    }
+}
+
+/// Add `(weight, value)` pairs, each one being added with [RandomSelector::with].
+///
+/// The weight of None is kept unchanged. The weights aren't checked: use
+/// [RandomSelector::try_extend] if they may be negative, NaN or infinite.
+impl<T, W: Weight> Extend<(W, T)> for RandomSelector<T, W> {
+    fn extend<I: IntoIterator<Item = (W, T)>>(&mut self, iter: I) {
+        for (weight, value) in iter {
+            self.push(weight, value);
+        }
+    }
+}
+
+impl<T, W: Weight> RandomSelector<T, W> {
+    /// Build a selector from `(weight, value)` pairs, checking the weights
+    /// like [Self::try_with].
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let selector = RandomSelector::try_from_iter([(1.0, 'A'), (2.0, 'B')])?;
+    /// assert_eq!(selector.total_weight(), 3.0);
+    /// assert_eq!(
+    ///     RandomSelector::try_from_iter([(1.0, 'A'), (f64::NAN, 'B')]).err(),
+    ///     Some(SelectorError::NanWeight),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, SelectorError>
+    where
+        I: IntoIterator<Item = (W, T)>,
+    {
+        let mut selector = Self::default();
+        selector.try_extend(iter)?;
+        Ok(selector)
+    }
+    /// Add `(weight, value)` pairs, checking the weights like [Self::try_with].
+    ///
+    /// The weight of None is kept unchanged. On error, the pairs before the
+    /// invalid one are added.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let mut selector = RandomSelector::default().with(1.0, 'A');
+    /// assert_eq!(
+    ///     selector.try_extend([(2.0, 'B'), (-1.0, 'C')]),
+    ///     Err(SelectorError::NegativeWeight(-1.0)),
+    /// );
+    /// assert_eq!(selector.len(), 2);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), SelectorError>
+    where
+        I: IntoIterator<Item = (W, T)>,
+    {
+        for (weight, value) in iter {
+            let weight = self.check_weight(weight, None)?;
+            self.push(weight, value);
+        }
+        Ok(())
+    }
+    /// Build a selector from values and their weights, checking the weights
+    /// like [Self::try_with] and that there are as many weights as values.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let selector = RandomSelector::from_weights(['A', 'B', 'C'], [1u32, 2, 3])?;
+    /// assert_eq!(selector.probability_of(2), Some(0.5));
+    /// assert_eq!(
+    ///     RandomSelector::from_weights(['A', 'B'], [1.0]).err(),
+    ///     Some(SelectorError::LengthMismatch { expected: 2, found: 1 }),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn from_weights<V, I>(values: V, weights: I) -> Result<Self, SelectorError>
+    where
+        V: IntoIterator<Item = T>,
+        I: IntoIterator<Item = W>,
+    {
+        let values: Vec<T> = values.into_iter().collect();
+        let weights: Vec<W> = weights.into_iter().collect();
+        if weights.len() != values.len() {
+            return Err(SelectorError::LengthMismatch {
+                expected: values.len(),
+                found: weights.len(),
+            });
+        }
+        weights
+            .into_iter()
+            .zip(values)
+            .try_fold(Self::default(), |selector, (weight, value)| {
+                selector.try_with(weight, value)
+            })
+    }
+    /// Build a selector giving the same chance to all values
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector: RandomSelector<char> = RandomSelector::uniform(['A', 'B', 'C', 'D']);
+    /// assert_eq!(selector.probability_of(3), Some(0.25));
+    /// ```
+    pub fn uniform<V: IntoIterator<Item = T>>(values: V) -> Self {
+        values.into_iter().map(|value| (W::ONE, value)).collect()
+    }
+    /// Build a selector from a map of values to weights, checking the weights
+    /// like [Self::try_with].
+    ///
+    /// The order of the choices is the iteration order of the map.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let weights = HashMap::from([("sword", 1.0), ("shield", 3.0)]);
+    /// let selector = RandomSelector::from_map(weights)?;
+    /// assert_eq!(selector.total_weight(), 4.0);
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn from_map<S: BuildHasher>(map: HashMap<T, W, S>) -> Result<Self, SelectorError> {
+        map.into_iter()
+            .try_fold(Self::default(), |selector, (value, weight)| {
+                selector.try_with(weight, value)
+            })
+    }
+}
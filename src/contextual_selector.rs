@@ -0,0 +1,167 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// The weight of a choice of a [ContextualSelector]
+type WeightFn<C> = Box<dyn Fn(&C) -> f64>;
+
+/// A selector whose weights may depend on a context (player level, biome, etc.)
+/// given at draw time.
+///
+/// The effective weights are computed on the first draw for a context, then
+/// reused as long as the context doesn't change.
+///
+/// As the weights computed for a context can't be checked when the selector
+/// is built, they're checked like with [RandomSelector::try_with] when they're
+/// used, and an invalid weight makes the draw return an error.
+///
+/// ```
+/// use rand_select::{ContextualSelector, SelectorError};
+/// let mut selector = ContextualSelector::default()
+///     .with(10.0, "rat")
+///     .with_fn(|level: &i32| f64::from(*level), "dragon");
+/// let mut rng = rand::rng();
+/// assert_eq!(selector.select_with(&0, &mut rng)?, Some(&"rat"));
+/// assert_eq!(selector.probability_of(&30, 1)?, Some(0.75));
+/// assert_eq!(
+///     selector.select_with(&-1, &mut rng),
+///     Err(SelectorError::NegativeWeight(-1.0)),
+/// );
+/// # Ok::<(), SelectorError>(())
+/// ```
+pub struct ContextualSelector<C, T> {
+    choices: Vec<(WeightFn<C>, T)>,
+    none_weight: NoneWeight<f64>,
+    strategy: Strategy,
+    /// The last context, with the selector of the indexes of the choices
+    /// built for it
+    cache: Option<(C, RandomSelector<usize>)>,
+}
+
+impl<C, T> Default for ContextualSelector<C, T> {
+    fn default() -> Self {
+        Self {
+            choices: Vec::new(),
+            none_weight: NoneWeight::default(),
+            strategy: Strategy::default(),
+            cache: None,
+        }
+    }
+}
+
+impl<C, T> ContextualSelector<C, T> {
+    /// Add a choice with a fixed weight.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn with(self, weight: f64, value: T) -> Self {
+        let weight = weight.abs();
+        self.with_fn(move |_| weight, value)
+    }
+    /// Add a choice whose weight is computed from the context
+    pub fn with_fn<F>(mut self, weight: F, value: T) -> Self
+    where
+        F: Fn(&C) -> f64 + 'static,
+    {
+        self.choices.push((Box::new(weight), value));
+        self.cache = None;
+        self
+    }
+    /// Add a weight for which no value is selected
+    pub fn with_none(mut self, weight: f64) -> Self {
+        self.none_weight = self.none_weight.add(weight.abs());
+        self.cache = None;
+        self
+    }
+    /// Complete choices to be None up to the given weight, whatever the context.
+    ///
+    /// If the total is smaller than the sum of the weights for a context, the
+    /// last choices can't be fully reached.
+    pub fn with_none_up_to(mut self, total_weight: f64) -> Self {
+        self.none_weight = NoneWeight::UpTo(total_weight.abs());
+        self.cache = None;
+        self
+    }
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self.cache = None;
+        self
+    }
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.choices.get(index).map(|(_, value)| value)
+    }
+    /// Return the weight of the choice at the given index, for the context,
+    /// without checking it
+    pub fn weight(&self, ctx: &C, index: usize) -> Option<f64> {
+        self.choices.get(index).map(|(weight, _)| weight(ctx))
+    }
+    /// Build the selector of the indexes of the choices for the context,
+    /// checking the weights
+    fn build_selector(&self, ctx: &C) -> Result<RandomSelector<usize>, SelectorError> {
+        let selector = RandomSelector::try_from_iter(
+            self.choices
+                .iter()
+                .enumerate()
+                .map(|(index, (weight, _))| (weight(ctx), index)),
+        )?
+        .with_strategy(self.strategy);
+        match self.none_weight {
+            NoneWeight::Added(weight) => selector.try_with_none(weight),
+            NoneWeight::UpTo(total) => {
+                Total::check(total).map(|total| selector.with_none_up_to(total))
+            }
+        }
+    }
+    /// Return the probability of the choice at the given index, for the context.
+    ///
+    /// The weights are computed again on every call.
+    pub fn probability_of(&self, ctx: &C, index: usize) -> Result<Option<f64>, SelectorError> {
+        Ok(self.build_selector(ctx)?.probability_of(index))
+    }
+    /// Return the probability of getting None, for the context.
+    ///
+    /// The weights are computed again on every call.
+    pub fn none_probability(&self, ctx: &C) -> Result<f64, SelectorError> {
+        Ok(self.build_selector(ctx)?.none_probability())
+    }
+}
+
+impl<C: PartialEq + Clone, T> ContextualSelector<C, T> {
+    /// Return the selector of the indexes of the choices for the context,
+    /// building it only if the context changed since the last call
+    fn selector_for(&mut self, ctx: &C) -> Result<&RandomSelector<usize>, SelectorError> {
+        if !matches!(&self.cache, Some((cached, _)) if cached == ctx) {
+            self.cache = None;
+            let selector = self.build_selector(ctx)?;
+            self.cache = Some((ctx.clone(), selector));
+        }
+        Ok(&self.cache.as_ref().unwrap().1)
+    }
+    /// Select a random value according to the weights computed for the context.
+    ///
+    /// Return an error if a weight or the weight of None is NaN, infinite
+    /// or negative for this context.
+    ///
+    /// ```
+    /// use rand_select::{ContextualSelector, SelectorError};
+    /// let mut selector = ContextualSelector::default()
+    ///     .with_fn(|hp: &f64| 1.0 / hp, "potion");
+    /// let mut rng = rand::rng();
+    /// assert_eq!(selector.select_with(&2.0, &mut rng)?, Some(&"potion"));
+    /// assert_eq!(
+    ///     selector.select_with(&0.0, &mut rng),
+    ///     Err(SelectorError::InfiniteWeight),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn select_with<R: Rng>(&mut self, ctx: &C, r: R) -> Result<Option<&T>, SelectorError> {
+        let index = self.selector_for(ctx)?.select_with_rng(r).copied();
+        Ok(index.and_then(|index| self.get(index)))
+    }
+}
@@ -0,0 +1,211 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// A selector whose weights can be changed in O(log n), with draws in O(log n).
+///
+/// It's backed by a Fenwick tree, and is suited to workloads mixing weight
+/// updates and draws, where the index of a [RandomSelector] would have to be
+/// rebuilt after every change.
+///
+/// ```
+/// use rand_select::DynamicSelector;
+/// let mut selector = DynamicSelector::default()
+///    .with(1.0, 'A')
+///    .with(1.5, 'B')
+///    .with_none(3.0);
+/// selector.set_weight(1, 0.0);
+/// selector.push(2.0, 'C');
+/// let mut rng = rand::rng();
+/// for _ in 0..100 {
+///     assert_ne!(selector.select_with_rng(&mut rng), Some(&'B'));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DynamicSelector<T> {
+    values: Vec<T>,
+    /// The exact weights, from which the tree is periodically rebuilt
+    weights: Vec<f64>,
+    tree: FenwickTree,
+    none_weight: f64,
+    /// Number of updates since the tree was last built, as rounding
+    /// errors accumulate in the tree with updates
+    updates: usize,
+}
+
+impl<T> Default for DynamicSelector<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            weights: Vec::new(),
+            tree: FenwickTree::default(),
+            none_weight: 0.0,
+            updates: 0,
+        }
+    }
+}
+
+impl<T> DynamicSelector<T> {
+    /// Add a choice.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn with(mut self, weight: f64, value: T) -> Self {
+        self.push(weight, value);
+        self
+    }
+    /// Add a choice, checking its weight is finite and not negative.
+    pub fn try_with(self, weight: f64, value: T) -> Result<Self, SelectorError> {
+        let weight = Weight::check(weight)?;
+        Ok(self.with(weight, value))
+    }
+    /// Add a weight for which no value is selected.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn with_none(mut self, weight: f64) -> Self {
+        self.none_weight += weight.abs();
+        self
+    }
+    /// Add a weight for which no value is selected, checking it's finite
+    /// and not negative.
+    pub fn try_with_none(self, weight: f64) -> Result<Self, SelectorError> {
+        let weight = Weight::check(weight)?;
+        Ok(self.with_none(weight))
+    }
+    /// Complete the current choices to be None up to the given weight.
+    ///
+    /// The weight of None is computed once and doesn't change when the weights
+    /// of the choices are updated. If the total is smaller than the sum of the
+    /// weights, the weight of None is zero.
+    pub fn with_none_up_to(mut self, total_weight: f64) -> Self {
+        self.none_weight = (total_weight.abs() - self.tree.total()).max(0.0);
+        self
+    }
+    /// Complete the current choices to be None up to the given weight, checking
+    /// this total is valid and not smaller than the sum of the weights.
+    pub fn try_with_none_up_to(self, total_weight: f64) -> Result<Self, SelectorError> {
+        let weights_sum: f64 = self.weights.iter().sum();
+        let total_weight = check_total(total_weight, weights_sum)?;
+        Ok(self.with_none_up_to(total_weight))
+    }
+    /// Add a choice, in O(log n).
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn push(&mut self, weight: f64, value: T) {
+        let weight = weight.abs();
+        self.values.push(value);
+        self.weights.push(weight);
+        self.tree.push(weight);
+    }
+    /// Change the weight of the choice at the given index, in O(log n).
+    ///
+    /// A negative weight is taken as its absolute value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_weight(&mut self, index: usize, weight: f64) {
+        let weight = weight.abs();
+        let delta = weight - self.weights[index];
+        self.weights[index] = weight;
+        self.updates += 1;
+        if self.updates > self.weights.len() {
+            // amortized O(1): rebuilding is O(n) and done after n updates
+            self.tree = FenwickTree::from_weights(&self.weights);
+            self.updates = 0;
+        } else {
+            self.tree.add(index, delta);
+        }
+    }
+    /// Change the weight of the choice at the given index, checking the
+    /// weight is finite and not negative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn try_set_weight(&mut self, index: usize, weight: f64) -> Result<(), SelectorError> {
+        let weight = Weight::check(weight)?;
+        self.set_weight(index, weight);
+        Ok(())
+    }
+    /// Return the number of choices, not counting None
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    /// Return the total weight, including the weight of None
+    pub fn total_weight(&self) -> f64 {
+        self.tree.total() + self.none_weight
+    }
+    /// Return the value of the choice at the given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.values.get(index)
+    }
+    /// Return the weight of the choice at the given index
+    pub fn weight(&self, index: usize) -> Option<f64> {
+        self.weights.get(index).copied()
+    }
+    /// Select a random value among the provided ones.
+    pub fn select(&self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_with_rng(&mut rng)
+    }
+    /// Select a random value among the provided ones, with the generator of your choice.
+    pub fn select_with_rng<R: Rng>(&self, mut r: R) -> Option<&T> {
+        let total_weight = self.total_weight();
+        if total_weight <= 0.0 {
+            return None;
+        }
+        let random_value: f64 = r.random_range(0.0..total_weight);
+        let idx = self.tree.find(random_value);
+        // rounding errors may make the search land on a zero weight choice
+        match self.weights.get(idx) {
+            Some(&weight) if weight > 0.0 => Some(&self.values[idx]),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        rand::SeedableRng,
+        rand_chacha::ChaCha8Rng,
+    };
+
+    #[test]
+    fn draws_follow_the_weights_updated_across_rebuilds() {
+        let mut selector = DynamicSelector::default()
+            .with(1.0, 'A')
+            .with(1.0, 'B')
+            .with(1.0, 'C')
+            .with(1.0, 'D')
+            .with_none(4.0);
+        // 7 updates: the tree is rebuilt at the 5th one
+        let updates = [(0, 5.0), (1, 0.5), (2, 3.0), (3, 0.0), (1, 3.0), (2, 1.0), (0, 0.0)];
+        for (index, weight) in updates {
+            selector.set_weight(index, weight);
+        }
+        assert_eq!(selector.updates, 2);
+        assert_eq!(selector.total_weight(), 8.0);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut counts = [0usize; 5];
+        let draws = 80_000;
+        for _ in 0..draws {
+            let index = match selector.select_with_rng(&mut rng) {
+                Some(l) => (*l as u8 - b'A') as usize,
+                None => 4,
+            };
+            counts[index] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[3], 0);
+        for (count, weight) in [(counts[1], 3.0), (counts[2], 1.0), (counts[4], 4.0)] {
+            let expected = draws as f64 * weight / 8.0;
+            assert!((count as f64 - expected).abs() < expected * 0.03, "{counts:?}");
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use {
+    crate::*,
+    std::fmt,
+};
+
+/// Error raised when building a selector with invalid weights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectorError {
+    /// A weight is NaN
+    NanWeight,
+    /// A weight is infinite
+    InfiniteWeight,
+    /// A weight is negative
+    NegativeWeight(f64),
+    /// The total given to `with_none_up_to` is smaller than the sum of the weights
+    /// of the choices, which would make the last choices unreachable
+    TotalTooSmall { total: f64, weights_sum: f64 },
+    /// A list doesn't have the length of the list of choices
+    LengthMismatch { expected: usize, found: usize },
+    /// Nested tables are deeper than the given max depth
+    MaxDepthExceeded(usize),
+    /// A weight can't be used as a number of items, not being an integer
+    NotACount(f64),
+    /// The number of items is too big to be counted
+    CountOverflow,
+    /// The total weight is too big for the type in which weights are summed
+    WeightOverflow,
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NanWeight => write!(f, "weight is NaN"),
+            Self::InfiniteWeight => write!(f, "weight is infinite"),
+            Self::NegativeWeight(weight) => write!(f, "weight is negative: {weight}"),
+            Self::TotalTooSmall { total, weights_sum } => write!(
+                f,
+                "total weight {total} is smaller than the sum of the weights {weights_sum}",
+            ),
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "expected {expected} elements, found {found}",
+            ),
+            Self::MaxDepthExceeded(max_depth) => write!(
+                f,
+                "nested tables are deeper than the max depth {max_depth}",
+            ),
+            Self::NotACount(weight) => write!(f, "weight {weight} isn't an integer count"),
+            Self::CountOverflow => write!(f, "the number of items overflows"),
+            Self::WeightOverflow => write!(f, "the total weight overflows"),
+        }
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+/// Check the total weight is valid and not smaller than the sum of the weights
+pub(crate) fn check_total<S: Total>(total_weight: S, weights_sum: S) -> Result<S, SelectorError> {
+    let total_weight = total_weight.check()?;
+    if total_weight.is_below_sum(weights_sum) {
+        return Err(SelectorError::TotalTooSmall {
+            total: total_weight.to_f64(),
+            weights_sum: weights_sum.to_f64(),
+        });
+    }
+    Ok(total_weight)
+}
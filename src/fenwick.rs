@@ -0,0 +1,123 @@
+/// A Fenwick tree (binary indexed tree) of weights, allowing O(log n)
+/// updates, prefix sums and searches.
+///
+/// Node `i` holds the sum of the weights of indices `(i + 1 - lsb(i + 1))..=i`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FenwickTree {
+    nodes: Vec<f64>,
+}
+
+fn lsb(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl FenwickTree {
+    pub fn from_weights(weights: &[f64]) -> Self {
+        let mut nodes = weights.to_vec();
+        for i in 1..=nodes.len() {
+            let parent = i + lsb(i);
+            if parent <= nodes.len() {
+                nodes[parent - 1] += nodes[i - 1];
+            }
+        }
+        Self { nodes }
+    }
+    /// Append a weight
+    pub fn push(&mut self, weight: f64) {
+        let i = self.nodes.len() + 1;
+        let covered = self.prefix_sum(i - 1) - self.prefix_sum(i - lsb(i));
+        self.nodes.push(weight + covered);
+    }
+    /// Add `delta` to the weight at `index`
+    pub fn add(&mut self, index: usize, delta: f64) {
+        let mut i = index + 1;
+        while i <= self.nodes.len() {
+            self.nodes[i - 1] += delta;
+            i += lsb(i);
+        }
+    }
+    /// Return the sum of the `count` first weights
+    pub fn prefix_sum(&self, count: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut i = count;
+        while i > 0 {
+            sum += self.nodes[i - 1];
+            i -= lsb(i);
+        }
+        sum
+    }
+    pub fn total(&self) -> f64 {
+        self.prefix_sum(self.nodes.len())
+    }
+    /// Return the smallest index whose prefix sum, including it, exceeds `value`,
+    /// or the length of the tree when there's none
+    pub fn find(&self, mut value: f64) -> usize {
+        let mut pos = 0;
+        let mut step = match self.nodes.len() {
+            0 => 0,
+            len => 1 << len.ilog2(),
+        };
+        while step > 0 {
+            let next = pos + step;
+            if next <= self.nodes.len() && self.nodes[next - 1] <= value {
+                value -= self.nodes[next - 1];
+                pos = next;
+            }
+            step >>= 1;
+        }
+        pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check the tree against naive prefix sums of the weights, which are
+    /// small integers so that all sums are exact
+    fn check(tree: &FenwickTree, weights: &[f64]) {
+        let mut sum = 0.0;
+        assert_eq!(tree.prefix_sum(0), 0.0);
+        for (i, weight) in weights.iter().enumerate() {
+            sum += weight;
+            assert_eq!(tree.prefix_sum(i + 1), sum, "prefix sum of {} weights", i + 1);
+        }
+        assert_eq!(tree.total(), sum);
+        for value in 0..sum as usize {
+            let value = value as f64 + 0.5;
+            let mut expected = 0;
+            let mut prefix = weights[0];
+            while prefix <= value {
+                expected += 1;
+                prefix += weights[expected];
+            }
+            assert_eq!(tree.find(value), expected, "find {value}");
+        }
+        assert_eq!(tree.find(sum), weights.len());
+    }
+
+    #[test]
+    fn interleaved_pushes_and_adds_match_naive_sums() {
+        let mut tree = FenwickTree::default();
+        let mut weights = Vec::new();
+        for i in 0..40 {
+            let weight = ((i * 7) % 5) as f64;
+            tree.push(weight);
+            weights.push(weight);
+            check(&tree, &weights);
+            if i % 3 == 0 {
+                let index = (i * 11) % weights.len();
+                tree.add(index, 2.0);
+                weights[index] += 2.0;
+                check(&tree, &weights);
+            }
+            if i % 4 == 1 {
+                let index = i / 2;
+                tree.add(index, -weights[index]);
+                weights[index] = 0.0;
+                check(&tree, &weights);
+            }
+        }
+        check(&FenwickTree::from_weights(&weights), &weights);
+    }
+}
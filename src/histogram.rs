@@ -0,0 +1,116 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// The z-score of a two-sided 95% confidence level
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// The observed hits of a choice, or of None, compared to its probability
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBin {
+    pub count: usize,
+    /// The observed frequency of hits
+    pub frequency: f64,
+    /// The probability of a hit, as given by the weights
+    pub expected: f64,
+    /// The 95% confidence interval of the probability, computed from
+    /// the observed frequency (Wilson score interval)
+    pub confidence_interval: (f64, f64),
+}
+
+impl HistogramBin {
+    fn new(count: usize, samples: usize, expected: f64) -> Self {
+        if samples == 0 {
+            return Self {
+                count,
+                frequency: 0.0,
+                expected,
+                confidence_interval: (0.0, 1.0),
+            };
+        }
+        let n = samples as f64;
+        let frequency = count as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let denominator = 1.0 + z2 / n;
+        let center = (frequency + z2 / (2.0 * n)) / denominator;
+        let half_width = Z_95
+            * (frequency * (1.0 - frequency) / n + z2 / (4.0 * n * n)).sqrt()
+            / denominator;
+        Self {
+            count,
+            frequency,
+            expected,
+            confidence_interval: ((center - half_width).max(0.0), (center + half_width).min(1.0)),
+        }
+    }
+    /// Return the difference between the observed frequency and the probability
+    pub fn deviation(&self) -> f64 {
+        self.frequency - self.expected
+    }
+    /// Tell whether the probability is in the confidence interval
+    pub fn is_consistent(&self) -> bool {
+        let (low, high) = self.confidence_interval;
+        low <= self.expected && self.expected <= high
+    }
+}
+
+/// The result of many draws from a selector, built by [RandomSelector::simulate]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub samples: usize,
+    /// The bins of the choices, in the order of the choices
+    pub choices: Vec<HistogramBin>,
+    pub none: HistogramBin,
+}
+
+impl Histogram {
+    /// Iterate over the bins of the choices, then of None
+    pub fn bins(&self) -> impl Iterator<Item = &HistogramBin> {
+        self.choices.iter().chain(std::iter::once(&self.none))
+    }
+    /// Return the biggest absolute difference between an observed frequency
+    /// and the matching probability, None included
+    pub fn max_deviation(&self) -> f64 {
+        self.bins()
+            .map(|bin| bin.deviation().abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+impl<T, W: Weight> RandomSelector<T, W> {
+    /// Draw values with the generator of your choice and count the hits
+    /// of every choice and of None.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///     .with(1.0, "gold")
+    ///     .with(3.0, "silver")
+    ///     .with_none(4.0);
+    /// let histogram = selector.simulate(100_000, rand::rng());
+    /// assert_eq!(histogram.choices[1].expected, 0.375);
+    /// assert_eq!(histogram.none.expected, 0.5);
+    /// assert!(histogram.max_deviation() < 0.02);
+    /// ```
+    pub fn simulate<R: Rng>(&self, samples: usize, mut r: R) -> Histogram {
+        let mut counts = vec![0; self.len()];
+        let mut none_count = 0;
+        for _ in 0..samples {
+            match self.select_index(&mut r) {
+                Some(index) => counts[index] += 1,
+                None => none_count += 1,
+            }
+        }
+        let choices = counts
+            .iter()
+            .zip(self.probabilities())
+            .map(|(&count, (_, _, probability))| HistogramBin::new(count, samples, probability))
+            .collect();
+        Histogram {
+            samples,
+            choices,
+            none: HistogramBin::new(none_count, samples, self.none_probability()),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// Up to this number of choices, the `Auto` strategy walks the choices
+/// instead of building an array of cumulated weights
+const AUTO_LINEAR_MAX_LEN: usize = 16;
+
+/// Number of bits of the fixed-point bounds of `Strategy::StableV1`
+const STABLE_V1_BITS: u32 = 53;
+
+/// The lookup structure of a RandomSelector, built once from its choices
+/// according to its strategy.
+#[derive(Debug, Clone)]
+pub(crate) enum Index<W: Weight> {
+    Linear,
+    /// The cumulated weights of the choices
+    Cumulative(Vec<W::Total>),
+    /// An alias table whose outcomes are the choices, then None
+    Alias(AliasTable<W::Total>),
+    /// The fixed-point upper bounds of the choices, for `Strategy::StableV1`
+    StableV1(Vec<u64>),
+}
+
+impl<W: Weight> Index<W> {
+    pub fn new<T>(
+        strategy: Strategy,
+        choices: &[Choice<T, W>],
+        total_weight: W::Total,
+    ) -> Self {
+        match strategy {
+            Strategy::Auto if choices.len() <= AUTO_LINEAR_MAX_LEN => Self::Linear,
+            Strategy::Linear => Self::Linear,
+            Strategy::Auto | Strategy::Cumulative => {
+                // the sums are computed in the same order than in the linear walk,
+                // so that both strategies select the same value for the same draw
+                let mut cumulative_weight = W::Total::ZERO;
+                let cumulative_weights = choices
+                    .iter()
+                    .map(|choice| {
+                        cumulative_weight = cumulative_weight + choice.weight.to_total();
+                        cumulative_weight
+                    })
+                    .collect();
+                Self::Cumulative(cumulative_weights)
+            }
+            Strategy::Alias => {
+                let mut weights: Vec<W::Total> =
+                    reachable_weights(choices, total_weight).collect();
+                let reachable_sum = weights
+                    .iter()
+                    .fold(W::Total::ZERO, |sum, &weight| sum + weight);
+                weights.push(total_weight.sub_or_zero(reachable_sum));
+                match AliasTable::new(&weights, total_weight) {
+                    Some(table) => Self::Alias(table),
+                    // the integer weights are too big to be scaled
+                    None => Self::new(Strategy::Cumulative, choices, total_weight),
+                }
+            }
+            Strategy::StableV1 => {
+                // don't change this computation: draws must stay the same
+                // across versions and platforms
+                let scale = (1u64 << STABLE_V1_BITS) as f64;
+                let total = total_weight.to_f64();
+                let mut cumulative_weight = W::Total::ZERO;
+                let bounds = choices
+                    .iter()
+                    .map(|choice| {
+                        cumulative_weight = cumulative_weight + choice.weight.to_total();
+                        let ratio = cumulative_weight.to_f64().min(total) / total;
+                        (ratio * scale).floor() as u64
+                    })
+                    .collect();
+                Self::StableV1(bounds)
+            }
+        }
+    }
+    /// Return the index of the selected choice, or None
+    ///
+    /// The total weight must be positive.
+    pub fn select<T, R: Rng + ?Sized>(
+        &self,
+        choices: &[Choice<T, W>],
+        total_weight: W::Total,
+        rng: &mut R,
+    ) -> Option<usize> {
+        match self {
+            Self::Linear | Self::Cumulative(_) => {
+                let random_value = total_weight.random_below(rng);
+                self.locate(choices, random_value)
+            }
+            Self::Alias(table) => {
+                let idx = table.draw(rng);
+                (idx < choices.len()).then_some(idx)
+            }
+            Self::StableV1(bounds) => {
+                let random_value = rng.next_u64() >> (64 - STABLE_V1_BITS);
+                let idx = bounds.partition_point(|&b| b <= random_value);
+                (idx < bounds.len()).then_some(idx)
+            }
+        }
+    }
+    /// Return the index of the choice whose weight range, in the
+    /// cumulated weights, contains the given value
+    pub fn locate<T>(
+        &self,
+        choices: &[Choice<T, W>],
+        random_value: W::Total,
+    ) -> Option<usize> {
+        match self {
+            Self::Cumulative(cumulative_weights) => {
+                let idx = cumulative_weights.partition_point(|&c| c <= random_value);
+                (idx < cumulative_weights.len()).then_some(idx)
+            }
+            // the other structures can't locate a weight, so we walk the choices
+            Self::Linear | Self::Alias(_) | Self::StableV1(_) => {
+                let mut cumulative_weight = W::Total::ZERO;
+                for (idx, choice) in choices.iter().enumerate() {
+                    cumulative_weight = cumulative_weight + choice.weight.to_total();
+                    if random_value < cumulative_weight {
+                        return Some(idx);
+                    }
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Compute the weights of the choices, as really reachable by a draw in
+/// `[0, total_weight)`: when the total is smaller than the sum of the weights,
+/// the last choices are partially or totally out of reach.
+pub(crate) fn reachable_weights<T, W: Weight>(
+    choices: &[Choice<T, W>],
+    total_weight: W::Total,
+) -> impl Iterator<Item = W::Total> {
+    let min = |a: W::Total, b: W::Total| if a < b { a } else { b };
+    let mut cumulative_weight = W::Total::ZERO;
+    choices
+        .iter()
+        .map(move |choice| {
+            let start = min(cumulative_weight, total_weight);
+            cumulative_weight = cumulative_weight + choice.weight.to_total();
+            min(cumulative_weight, total_weight).sub_or_zero(start)
+        })
+}
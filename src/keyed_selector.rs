@@ -0,0 +1,229 @@
+use {
+    crate::*,
+    rand::Rng,
+    std::{
+        borrow::Borrow,
+        collections::HashMap,
+        hash::Hash,
+    },
+};
+
+/// A selector whose choices are associated to keys, for example item ids,
+/// allowing to find, update or remove them by key.
+///
+/// ```
+/// use rand_select::KeyedSelector;
+/// let mut selector = KeyedSelector::default()
+///    .with("sword", 1.0, "A shiny sword")
+///    .with("shield", 1.5, "A heavy shield")
+///    .with_none(3.0);
+/// selector.set_weight("sword", 3.0);
+/// assert!(selector.contains("shield"));
+/// selector.remove("shield");
+/// assert_eq!(selector.selector().probability_of(0), Some(0.5));
+/// if let Some(key) = selector.select_key() {
+///     assert_eq!(key, &"sword");
+/// }
+/// ```
+#[derive(Clone)]
+pub struct KeyedSelector<K, T, W: Weight = f64> {
+    selector: RandomSelector<(K, T), W>,
+    /// The index of each key in the choices of the selector
+    positions: HashMap<K, usize>,
+}
+
+impl<K, T, W: Weight> Default for KeyedSelector<K, T, W> {
+    fn default() -> Self {
+        Self {
+            selector: RandomSelector::default(),
+            positions: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, T, W: Weight> KeyedSelector<K, T, W> {
+    /// Add a choice, or replace the one having the same key.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn with(mut self, key: K, weight: W, value: T) -> Self {
+        self.insert(key, weight, value);
+        self
+    }
+    /// Add a choice, or replace the one having the same key, checking the
+    /// weight is finite and not negative.
+    pub fn try_with(self, key: K, weight: W, value: T) -> Result<Self, SelectorError> {
+        let index = self.positions.get(&key).copied();
+        let weight = self.selector.check_weight(weight, index)?;
+        Ok(self.with(key, weight, value))
+    }
+    /// Add a weight for which no value is selected.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn with_none(mut self, weight: W::Total) -> Self {
+        self.selector = self.selector.with_none(weight);
+        self
+    }
+    /// Add a weight for which no value is selected, checking it's finite
+    /// and not negative.
+    pub fn try_with_none(mut self, weight: W::Total) -> Result<Self, SelectorError> {
+        self.selector = self.selector.try_with_none(weight)?;
+        Ok(self)
+    }
+    /// Complete choices to be None up to the given weight.
+    pub fn with_none_up_to(mut self, total_weight: W::Total) -> Self {
+        self.selector = self.selector.with_none_up_to(total_weight);
+        self
+    }
+    /// Complete choices to be None up to the given weight, checking this total
+    /// is valid and not smaller than the sum of the weights of the choices.
+    pub fn try_with_none_up_to(mut self, total_weight: W::Total) -> Result<Self, SelectorError> {
+        self.selector = self.selector.try_with_none_up_to(total_weight)?;
+        Ok(self)
+    }
+    /// Set the algorithm used to select values (default is `Strategy::Auto`).
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.selector = self.selector.with_strategy(strategy);
+        self
+    }
+    /// Add a choice, or replace the one having the same key, in which case
+    /// the previous value is returned.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn insert(&mut self, key: K, weight: W, value: T) -> Option<T> {
+        if let Some(&idx) = self.positions.get(&key) {
+            self.selector.set_weight(idx, weight);
+            let entry = self.selector.get_mut(idx)?;
+            return Some(std::mem::replace(&mut entry.1, value));
+        }
+        self.positions.insert(key.clone(), self.selector.len());
+        self.selector.push(weight, (key, value));
+        None
+    }
+    /// Change the weight of the choice with the given key, and return its
+    /// previous weight, or None if there's no such key.
+    ///
+    /// A negative weight is taken as its absolute value.
+    pub fn set_weight<Q>(&mut self, key: &Q, weight: W) -> Option<W>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.positions.get(key)?;
+        let previous = self.selector.weight(idx);
+        self.selector.set_weight(idx, weight);
+        previous
+    }
+    /// Remove the choice with the given key, and return its weight and value.
+    ///
+    /// The weight of None is kept unchanged.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<(W, T)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = self.positions.remove(key)?;
+        let weight = self.selector.weight(idx)?;
+        let (_, value) = self.selector.remove(idx);
+        for position in self.positions.values_mut() {
+            if *position > idx {
+                *position -= 1;
+            }
+        }
+        Some((weight, value))
+    }
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+    /// Return the value of the choice with the given key
+    pub fn get<Q>(&self, key: &Q) -> Option<&T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.positions.get(key)?;
+        self.selector.get(idx).map(|(_, value)| value)
+    }
+    /// Return the weight of the choice with the given key
+    pub fn weight<Q>(&self, key: &Q) -> Option<W>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let idx = *self.positions.get(key)?;
+        self.selector.weight(idx)
+    }
+    /// Return the number of choices, not counting None
+    pub fn len(&self) -> usize {
+        self.selector.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.selector.is_empty()
+    }
+    /// Return the underlying selector, whose values are `(key, value)` pairs
+    pub fn selector(&self) -> &RandomSelector<(K, T), W> {
+        &self.selector
+    }
+    /// Select a random key
+    pub fn select_key(&self) -> Option<&K> {
+        let mut rng = rand::rng();
+        self.select_key_with_rng(&mut rng)
+    }
+    /// Select a random key, with the generator of your choice
+    pub fn select_key_with_rng<R: Rng>(&self, r: R) -> Option<&K> {
+        self.select_entry_with_rng(r).map(|(key, _)| key)
+    }
+    /// Select a random value
+    pub fn select(&self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_with_rng(&mut rng)
+    }
+    /// Select a random value, with the generator of your choice
+    pub fn select_with_rng<R: Rng>(&self, r: R) -> Option<&T> {
+        self.select_entry_with_rng(r).map(|(_, value)| value)
+    }
+    /// Select a random choice, with the generator of your choice, and
+    /// return its key and value
+    pub fn select_entry_with_rng<R: Rng>(&self, r: R) -> Option<(&K, &T)> {
+        self.selector
+            .select_with_rng(r)
+            .map(|(key, value)| (key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        rand::SeedableRng,
+        rand_chacha::ChaCha8Rng,
+    };
+
+    #[test]
+    fn keys_after_a_removed_one_are_still_found() {
+        let mut selector = KeyedSelector::default()
+            .with("a", 1u32, 'A')
+            .with("b", 2, 'B')
+            .with("c", 3, 'C')
+            .with("d", 4, 'D');
+        assert_eq!(selector.remove("b"), Some((2, 'B')));
+        assert_eq!(selector.len(), 3);
+        assert!(!selector.contains("b"));
+        assert_eq!(selector.get("a"), Some(&'A'));
+        assert_eq!(selector.get("c"), Some(&'C'));
+        assert_eq!(selector.weight("d"), Some(4));
+        assert_eq!(selector.set_weight("c", 0), Some(3));
+        assert_eq!(selector.set_weight("d", 0), Some(4));
+        assert_eq!(selector.weight("a"), Some(1));
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        assert!((0..100).all(|_| selector.select_key_with_rng(&mut rng) == Some(&"a")));
+        selector.set_weight("a", 0);
+        selector.set_weight("d", 1);
+        assert!((0..100).all(|_| selector.select_key_with_rng(&mut rng) == Some(&"d")));
+        assert_eq!(selector.remove("d"), Some((1, 'D')));
+        assert_eq!(selector.get("c"), Some(&'C'));
+    }
+}
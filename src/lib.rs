@@ -23,86 +23,95 @@
 //!    .with_none_up_to(1.0);
 //! ```
 //! The RandomSelector is designed for reuse, and can use the RNG of your choice.
+//!
+//! Weights can also be integers, summed and drawn with exact arithmetic:
+//!
+//! ```
+//! use rand_select::RandomSelector;
+//! let selector = RandomSelector::default()
+//!    .with(1u32, 'A')
+//!    .with(2, 'B');
+//! // 'A' has exactly one chance out of three
+//! assert_eq!(selector.probability_of(0), Some(1.0 / 3.0));
+//! ```
+//!
+//! For big tables from which many values are drawn, the `Alias` strategy gives constant time selection.
+//!
+//! When draws must be reproduced exactly, for replays, the `StableV1` strategy gives the same values for a given RNG stream on all platforms and in all versions of the crate.
+//!
+//! With the `serde` feature, a RandomSelector can be serialized and deserialized, its weights
+//! being checked on deserialization:
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use rand_select::RandomSelector;
+//! let selector: RandomSelector<String> = serde_json::from_str(r#"{
+//!     "choices": [
+//!         { "weight": 0.1, "value": "sword" },
+//!         { "weight": 0.2, "value": "shield" }
+//!     ],
+//!     "none_up_to": 1.0
+//! }"#).unwrap();
+//! assert_eq!(selector.none_probability(), 0.7);
+//! # }
+//! ```
+//!
+//! With the `testing` feature, `test_fairness` draws many values from a selector, with the RNG of your choice, and runs chi-squared and G-tests against the expected probabilities, so that you can assert in your CI that your tables and RNGs are unbiased.
 
-use {
-    rand::Rng,
-};
-
-#[derive(Clone)]
-struct Choice<T> {
-    weight: f64,
-    value: T,
-}
+mod alias;
+mod choice;
+mod collections;
+mod contextual_selector;
+mod dynamic_selector;
+mod error;
+mod fenwick;
+mod histogram;
+mod index;
+mod keyed_selector;
+mod nested;
+mod none_policy;
+mod none_weight;
+mod pity_selector;
+mod prd_selector;
+mod random_selector;
+mod sample_iter;
+mod seeded_selector;
+mod selected;
+#[cfg(feature = "serde")]
+mod serialization;
+mod shuffle_bag;
+mod strategy;
+#[cfg(feature = "testing")]
+mod testing;
+mod weight;
 
-/// A selector allowing to randomly select a value from a set of choices, each with an associated weight.
-///
-/// ```
-/// use rand_select::RandomSelector;
-/// let selector = RandomSelector::default()
-///    .with(1.0, 'A')
-///    .with(1.5, 'B')
-///    .with_none(3.0);
-/// let l = selector.select();
-/// // l has half a chance to be None, and is 50% more likely to be 'B' than 'A'
-/// ```
-#[derive(Clone)]
-pub struct RandomSelector<T> {
-    choices: Vec<Choice<T>>,
-    total_weight: f64,
-}
+pub use {
+    contextual_selector::*,
+    dynamic_selector::*,
+    error::*,
+    histogram::*,
+    keyed_selector::*,
+    nested::*,
+    none_policy::*,
+    pity_selector::*,
+    prd_selector::*,
+    random_selector::*,
+    sample_iter::*,
+    seeded_selector::*,
+    selected::*,
+    shuffle_bag::*,
+    strategy::*,
+    weight::*,
+};
 
-impl<T> Default for RandomSelector<T> {
-    fn default() -> Self {
-        Self {
-            choices: Vec::new(),
-            total_weight: 0.0,
-        }
-    }
-}
+#[cfg(feature = "testing")]
+pub use testing::*;
 
-impl<T> RandomSelector<T> {
-    pub fn with(mut self, weight: f64, value: T) -> Self {
-        self.choices.push(Choice { weight, value });
-        self.total_weight += weight.abs();
-        self
-    }
-    pub fn with_none(mut self, weight: f64) -> Self {
-        self.total_weight += weight.abs();
-        self
-    }
-    /// Complete choices to be None up to the given weight.
-    ///
-    /// This is convenient where all choices set are conventionnaly already normalized:
-    ///
-    /// ```
-    /// use rand_select::RandomSelector;
-    /// let selector = RandomSelector::default()
-    ///    .with(0.1, 'A')
-    ///    .with(0.2, 'B')
-    ///    .with_none_up_to(1.0);
-    /// ```
-    pub fn with_none_up_to(mut self, total_weight: f64) -> Self {
-        self.total_weight = total_weight.abs();
-        self
-    }
-    /// Select a random value among the provided ones.
-    pub fn select(&self) -> Option<&T> {
-        let mut rng = rand::rng();
-        self.select_with_rng(&mut rng)
-    }
-    /// Select a random value among the provided ones, with the generator of your choice.
-    pub fn select_with_rng<R: Rng>(&self, mut r: R) -> Option<&T> {
-        if self.total_weight == 0.0 {
-            return None;
-        }
-        let random_value: f64 = r.random_range(0.0..self.total_weight);
-        let mut cumulative_weight = 0.0;
-        for choice in &self.choices {
-            cumulative_weight += choice.weight;
-            if random_value < cumulative_weight {
-                return Some(&choice.value);
-            }
-        }
-        None
-    }
-}
+use {
+    alias::*,
+    choice::*,
+    fenwick::*,
+    index::*,
+    none_weight::*,
+};
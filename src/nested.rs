@@ -0,0 +1,106 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// The value of a choice in a hierarchical table: either a final value,
+/// or another table in which to select.
+///
+/// As a table owns its sub-tables, there can't be loops. The max depth
+/// given to [RandomSelector::flatten] guards against excessive nesting.
+///
+/// ```
+/// use rand_select::{Nested, RandomSelector};
+/// let rare = RandomSelector::default()
+///     .with(1.0, Nested::Leaf("crown"))
+///     .with(1.0, Nested::Leaf("scepter"));
+/// let common = RandomSelector::default()
+///     .with(3.0, Nested::Leaf("stick"))
+///     .with(1.0, Nested::Leaf("stone"));
+/// let loot = RandomSelector::default()
+///     .with(1.0, Nested::Table(rare))
+///     .with(9.0, Nested::Table(common));
+/// let item = loot.select_leaf();
+/// assert!(item.is_some());
+/// let flat = loot.flatten(8).unwrap();
+/// assert_eq!(flat.probability_of(0), Some(0.05)); // crown
+/// assert_eq!(flat.probability_of(2), Some(0.675)); // stick
+/// ```
+#[derive(Clone)]
+pub enum Nested<T, W: Weight = f64> {
+    Leaf(T),
+    Table(RandomSelector<Nested<T, W>, W>),
+}
+
+impl<T, W: Weight> RandomSelector<Nested<T, W>, W> {
+    /// Select a random final value, selecting in sub-tables until a leaf is found.
+    pub fn select_leaf(&self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_leaf_with_rng(&mut rng)
+    }
+    /// Select a random final value, with the generator of your choice,
+    /// selecting in sub-tables until a leaf is found.
+    ///
+    /// None is returned when None is selected in any table.
+    pub fn select_leaf_with_rng<R: Rng>(&self, mut r: R) -> Option<&T> {
+        let mut table = self;
+        loop {
+            match table.select_with_rng(&mut r)? {
+                Nested::Leaf(value) => return Some(value),
+                Nested::Table(sub_table) => table = sub_table,
+            }
+        }
+    }
+    /// Return the number of levels of tables, 1 when there's no sub-table
+    pub fn depth(&self) -> usize {
+        1 + self
+            .iter()
+            .map(|(_, value)| match value {
+                Nested::Leaf(_) => 0,
+                Nested::Table(sub_table) => sub_table.depth(),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<T: Clone, W: Weight> RandomSelector<Nested<T, W>, W> {
+    /// Compute the equivalent single-level selector, whose weights are the
+    /// probabilities of the leaves.
+    ///
+    /// Return an error if there are more than `max_depth` levels of tables.
+    pub fn flatten(&self, max_depth: usize) -> Result<RandomSelector<T>, SelectorError> {
+        let mut flat = RandomSelector::default().with_strategy(self.strategy);
+        let none_probability = self.flatten_into(&mut flat, 1.0, 1, max_depth)?;
+        Ok(flat.with_none(none_probability))
+    }
+    /// Add the leaves to the flat selector, with their probabilities multiplied
+    /// by the probability of this table, and return the probability of None
+    fn flatten_into(
+        &self,
+        flat: &mut RandomSelector<T>,
+        probability: f64,
+        depth: usize,
+        max_depth: usize,
+    ) -> Result<f64, SelectorError> {
+        if depth > max_depth {
+            return Err(SelectorError::MaxDepthExceeded(max_depth));
+        }
+        let mut none_probability = probability * self.none_probability();
+        for (value, _, value_probability) in self.probabilities() {
+            let value_probability = probability * value_probability;
+            match value {
+                Nested::Leaf(value) => flat.push(value_probability, value.clone()),
+                Nested::Table(sub_table) => {
+                    none_probability += sub_table.flatten_into(
+                        flat,
+                        value_probability,
+                        depth + 1,
+                        max_depth,
+                    )?;
+                }
+            }
+        }
+        Ok(none_probability)
+    }
+}
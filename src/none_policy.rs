@@ -0,0 +1,12 @@
+/// How the None weight is handled by draws which aren't simple selections,
+/// like [RandomSelector::select_many_distinct](crate::RandomSelector::select_many_distinct).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum NonePolicy {
+    /// None keeps its weight and may be drawn, which stops a draw of several values
+    #[default]
+    Keep,
+    /// None is never drawn, only values are
+    Skip,
+}
@@ -0,0 +1,49 @@
+use crate::*;
+
+/// The weight for which no value is selected, kept apart from the weights
+/// of the choices so that the total is always computed from them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum NoneWeight<S> {
+    /// A weight added to the sum of the weights of the choices
+    Added(S),
+    /// The total weight, None getting what the choices leave
+    UpTo(S),
+}
+
+impl<S: Total> Default for NoneWeight<S> {
+    fn default() -> Self {
+        Self::Added(S::ZERO)
+    }
+}
+
+impl<S: Total> NoneWeight<S> {
+    /// Add a weight to the one of None, saturating at the maximum for integers
+    pub fn add(self, weight: S) -> Self {
+        match self {
+            Self::Added(none_weight) => Self::Added(none_weight.saturating_add(weight)),
+            Self::UpTo(total_weight) => Self::UpTo(total_weight.saturating_add(weight)),
+        }
+    }
+    /// Add a weight to the one of None, or return None if it overflows
+    pub fn checked_add(self, weight: S) -> Option<Self> {
+        match self {
+            Self::Added(none_weight) => none_weight.checked_add(weight).map(Self::Added),
+            Self::UpTo(total_weight) => total_weight.checked_add(weight).map(Self::UpTo),
+        }
+    }
+    /// Return the total weight, knowing the sum of the weights of the choices,
+    /// saturating at the maximum for integers
+    pub fn total(self, weights_sum: S) -> S {
+        match self {
+            Self::Added(none_weight) => weights_sum.saturating_add(none_weight),
+            Self::UpTo(total_weight) => total_weight,
+        }
+    }
+    /// Return the total weight, or None if it overflows
+    pub fn checked_total(self, weights_sum: S) -> Option<S> {
+        match self {
+            Self::Added(none_weight) => weights_sum.checked_add(none_weight),
+            Self::UpTo(total_weight) => Some(total_weight),
+        }
+    }
+}
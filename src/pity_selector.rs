@@ -0,0 +1,256 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// The counters of a [PitySelector], to be persisted, per player for example.
+///
+/// It can be serialized with the `serde` feature.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PityState {
+    /// For each choice, the number of draws since it was last selected
+    pub misses: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Pity {
+    /// The choice is guaranteed on this draw since it was last selected
+    hard: Option<u32>,
+    /// From this draw since the choice was last selected, its weight
+    /// is increased by the increment on each draw
+    soft: Option<(u32, f64)>,
+}
+
+/// A stateful selector guaranteeing choices after a number of draws without them.
+///
+/// Each choice may have
+/// - a hard pity: it's selected on the n-th draw since it was last selected
+/// - a soft pity: starting from a given draw since it was last selected, its
+///   weight is increased by an increment for each draw
+///
+/// A choice can be a whole tier, for example a rarity.
+///
+/// ```
+/// use rand_select::{PitySelector, RandomSelector};
+/// let mut selector = PitySelector::new(
+///     RandomSelector::default()
+///         .with(0.01, "legendary")
+///         .with(0.99, "common")
+/// )
+/// .with_hard_pity(0, 10)
+/// .with_soft_pity(0, 5, 0.1);
+/// let mut rng = rand::rng();
+/// let legendaries = (0..100)
+///     .filter(|_| selector.select_with_rng(&mut rng) == Some(&"legendary"))
+///     .count();
+/// assert!(legendaries >= 10);
+/// ```
+#[derive(Clone)]
+pub struct PitySelector<T, W: Weight = f64> {
+    selector: RandomSelector<T, W>,
+    pities: Vec<Pity>,
+    state: PityState,
+}
+
+impl<T, W: Weight> From<RandomSelector<T, W>> for PitySelector<T, W> {
+    fn from(selector: RandomSelector<T, W>) -> Self {
+        Self::new(selector)
+    }
+}
+
+impl<T, W: Weight> PitySelector<T, W> {
+    pub fn new(selector: RandomSelector<T, W>) -> Self {
+        let len = selector.len();
+        Self {
+            selector,
+            pities: vec![Pity::default(); len],
+            state: PityState { misses: vec![0; len] },
+        }
+    }
+    /// Guarantee the choice at the given index on the n-th draw since
+    /// it was last selected.
+    ///
+    /// When several choices are guaranteed on the same draw, the first
+    /// one is selected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_hard_pity(mut self, index: usize, draws: u32) -> Self {
+        self.pities[index].hard = Some(draws);
+        self
+    }
+    /// Starting from the n-th draw since the choice at the given index was
+    /// last selected, increase its weight by `increment` on each draw.
+    ///
+    /// A negative increment is taken as its absolute value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn with_soft_pity(mut self, index: usize, start: u32, increment: f64) -> Self {
+        self.pities[index].soft = Some((start, increment.abs()));
+        self
+    }
+    /// Restore a state, previously obtained with [Self::state]
+    pub fn with_state(mut self, state: PityState) -> Result<Self, SelectorError> {
+        self.set_state(state)?;
+        Ok(self)
+    }
+    /// Restore a state, previously obtained with [Self::state]
+    pub fn set_state(&mut self, state: PityState) -> Result<(), SelectorError> {
+        if state.misses.len() != self.selector.len() {
+            return Err(SelectorError::LengthMismatch {
+                expected: self.selector.len(),
+                found: state.misses.len(),
+            });
+        }
+        self.state = state;
+        Ok(())
+    }
+    pub fn state(&self) -> &PityState {
+        &self.state
+    }
+    /// Reset all counters
+    pub fn reset(&mut self) {
+        self.state.misses.fill(0);
+    }
+    pub fn selector(&self) -> &RandomSelector<T, W> {
+        &self.selector
+    }
+    /// Return the weight of the choice at the given index for the next draw.
+    ///
+    /// It's the weight the choice can really be drawn with in the wrapped
+    /// selector (see [RandomSelector::probability_of]), increased by its soft pity.
+    pub fn effective_weight(&self, index: usize) -> Option<f64> {
+        let weight = self.reachable_weights().nth(index)?;
+        Some(self.with_soft_pity_of(index, weight))
+    }
+    fn reachable_weights(&self) -> impl Iterator<Item = f64> {
+        reachable_weights(&self.selector.choices, self.selector.total_weight())
+            .map(Total::to_f64)
+    }
+    /// Increase the weight of the choice at the given index by its soft pity
+    fn with_soft_pity_of(&self, index: usize, weight: f64) -> f64 {
+        let draw = self.state.misses[index].saturating_add(1);
+        match self.pities[index].soft {
+            Some((start, increment)) if draw > start => {
+                weight + increment * f64::from(draw - start)
+            }
+            _ => weight,
+        }
+    }
+    /// Select a random value, or None, updating the counters
+    pub fn select(&mut self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_with_rng(&mut rng)
+    }
+    /// Select a random value, or None, with the generator of your choice,
+    /// updating the counters
+    pub fn select_with_rng<R: Rng>(&mut self, mut r: R) -> Option<&T> {
+        let idx = self.select_index(&mut r);
+        for (i, misses) in self.state.misses.iter_mut().enumerate() {
+            *misses = if Some(i) == idx { 0 } else { misses.saturating_add(1) };
+        }
+        idx.and_then(|idx| self.selector.get(idx))
+    }
+    fn select_index<R: Rng>(&self, r: &mut R) -> Option<usize> {
+        let guaranteed = self.pities.iter().zip(&self.state.misses).position(
+            |(pity, &misses)| pity.hard.is_some_and(|draws| misses.saturating_add(1) >= draws),
+        );
+        if guaranteed.is_some() {
+            return guaranteed;
+        }
+        let weights: Vec<f64> = self
+            .reachable_weights()
+            .enumerate()
+            .map(|(idx, weight)| self.with_soft_pity_of(idx, weight))
+            .collect();
+        let none_weight = self.selector
+            .total_weight()
+            .sub_or_zero(self.selector.weights_sum())
+            .to_f64();
+        let total_weight = weights.iter().sum::<f64>() + none_weight;
+        if total_weight <= 0.0 {
+            return None;
+        }
+        let random_value: f64 = r.random_range(0.0..total_weight);
+        let mut cumulative_weight = 0.0;
+        weights.iter().position(|weight| {
+            cumulative_weight += weight;
+            random_value < cumulative_weight
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector() -> PitySelector<char> {
+        PitySelector::new(
+            RandomSelector::default()
+                .with(1.0, 'A')
+                .with(3.0, 'B')
+                .with_none(4.0),
+        )
+    }
+
+    #[test]
+    fn soft_pity_ramps_the_weight_up() {
+        let mut selector = selector().with_soft_pity(0, 3, 0.5);
+        let mut weights = Vec::new();
+        for misses in 0..6 {
+            selector.set_state(PityState { misses: vec![misses, misses] }).unwrap();
+            weights.push(selector.effective_weight(0).unwrap());
+            assert_eq!(selector.effective_weight(1), Some(3.0));
+        }
+        // the n-th draw since the last hit is the draw after n-1 misses
+        assert_eq!(weights, vec![1.0, 1.0, 1.0, 1.5, 2.0, 2.5]);
+        assert_eq!(selector.effective_weight(2), None);
+    }
+
+    #[test]
+    fn weights_are_the_reachable_ones() {
+        let selector = PitySelector::new(
+            RandomSelector::default()
+                .with(0.5, 'A')
+                .with(0.7, 'B')
+                .with_none_up_to(1.0),
+        );
+        assert_eq!(selector.effective_weight(0), Some(0.5));
+        assert_eq!(selector.effective_weight(1), Some(0.5));
+    }
+
+    #[test]
+    fn state_must_have_a_counter_per_choice() {
+        let mut selector = selector();
+        assert_eq!(
+            selector.set_state(PityState { misses: vec![1, 2, 3] }),
+            Err(SelectorError::LengthMismatch { expected: 2, found: 3 }),
+        );
+        assert_eq!(selector.state(), &PityState { misses: vec![0, 0] });
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut selector = selector().with_hard_pity(0, 5);
+        let mut rng = rand::rng();
+        for _ in 0..7 {
+            selector.select_with_rng(&mut rng);
+        }
+        let state = selector.state().clone();
+        let restored = self::selector()
+            .with_hard_pity(0, 5)
+            .with_state(state.clone())
+            .unwrap();
+        assert_eq!(restored.state(), &state);
+        assert_eq!(restored.effective_weight(0), selector.effective_weight(0));
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(serde_json::from_str::<PityState>(&json).unwrap(), state);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// A stateful selector using a pseudo-random distribution (PRD): the chance to
+/// get a value rises after each draw returning None, and is reset when a value
+/// is drawn.
+///
+/// The chance of the n-th draw after the last hit is `min(1, c * n)`, the
+/// constant `c` being calibrated so that the long-run rate of values matches
+/// the nominal one of the wrapped [RandomSelector]. In other words, the weight
+/// of None shrinks after each miss.
+///
+/// When a value is drawn, it's chosen among the values according to their weights.
+///
+/// ```
+/// use rand_select::{PrdSelector, RandomSelector};
+/// let mut selector = PrdSelector::new(
+///     RandomSelector::default()
+///         .with(0.25, "proc")
+///         .with_none_up_to(1.0)
+/// );
+/// let mut rng = rand::rng();
+/// let hits = (0..10_000)
+///     .filter(|_| selector.select_with_rng(&mut rng).is_some())
+///     .count();
+/// assert!(2000 < hits && hits < 3000);
+/// ```
+#[derive(Clone)]
+pub struct PrdSelector<T, W: Weight = f64> {
+    selector: RandomSelector<T, W>,
+    /// The sum of the weights of the values which can be reached
+    values_weight: W::Total,
+    nominal_probability: f64,
+    constant: f64,
+    misses: u32,
+}
+
+impl<T, W: Weight> From<RandomSelector<T, W>> for PrdSelector<T, W> {
+    fn from(selector: RandomSelector<T, W>) -> Self {
+        Self::new(selector)
+    }
+}
+
+impl<T, W: Weight> PrdSelector<T, W> {
+    /// Build a PRD selector whose long-run rate of values is the probability of
+    /// the given selector to return a value
+    pub fn new(selector: RandomSelector<T, W>) -> Self {
+        let nominal_probability = 1.0 - selector.none_probability();
+        let weights_sum = selector.weights_sum();
+        let values_weight = if weights_sum < selector.total_weight() {
+            weights_sum
+        } else {
+            selector.total_weight()
+        };
+        Self {
+            selector,
+            values_weight,
+            nominal_probability,
+            constant: prd_constant(nominal_probability),
+            misses: 0,
+        }
+    }
+    /// Return the probability, in the long run, to get a value
+    pub fn nominal_probability(&self) -> f64 {
+        self.nominal_probability
+    }
+    /// Return the calibrated constant `c`
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+    /// Return the probability that the next draw returns a value
+    pub fn chance(&self) -> f64 {
+        (self.constant * f64::from(self.misses + 1)).min(1.0)
+    }
+    /// Return the number of draws which returned None since the last hit
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+    /// Forget the previous misses
+    pub fn reset(&mut self) {
+        self.misses = 0;
+    }
+    pub fn selector(&self) -> &RandomSelector<T, W> {
+        &self.selector
+    }
+    /// Select a random value, or None, updating the chance of the next draw
+    pub fn select(&mut self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_with_rng(&mut rng)
+    }
+    /// Select a random value, or None, with the generator of your choice,
+    /// updating the chance of the next draw
+    pub fn select_with_rng<R: Rng>(&mut self, mut r: R) -> Option<&T> {
+        if self.nominal_probability <= 0.0 {
+            return None;
+        }
+        if r.random::<f64>() >= self.chance() {
+            self.misses = self.misses.saturating_add(1);
+            return None;
+        }
+        self.misses = 0;
+        self.selector
+            .select_index_below(self.values_weight, &mut r)
+            .and_then(|idx| self.selector.get(idx))
+    }
+}
+
+/// Below this probability, the PRD constant is given by its asymptotic
+/// expansion, as computing the rate of a constant takes about `1/p` steps
+const ASYMPTOTIC_MAX_P: f64 = 1e-3;
+
+/// Compute the PRD constant `c` giving the nominal probability `p` in the long run.
+///
+/// The rate of hits is the inverse of the expected number of draws to get a
+/// hit, and it increases with `c`, so `c` is found by bisection.
+///
+/// For small `p`, the expected number of draws is `sqrt(π/(2c)) - 1/3 + O(√c)`,
+/// which gives `c` with a relative error on the rate of about `p²/8`.
+fn prd_constant(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return p.clamp(0.0, 1.0);
+    }
+    if p < ASYMPTOTIC_MAX_P {
+        return std::f64::consts::FRAC_PI_2 / (1.0 / p + 1.0 / 3.0).powi(2);
+    }
+    let mut low = 0.0;
+    let mut high = p;
+    for _ in 0..64 {
+        let mid = (low + high) / 2.0;
+        if mid <= low || mid >= high {
+            break;
+        }
+        if prd_rate(mid) < p {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Compute the long-run rate of hits for the PRD constant `c`
+fn prd_rate(c: f64) -> f64 {
+    let mut expected_draws = 0.0;
+    let mut no_hit_yet = 1.0;
+    let mut n = 1.0;
+    while no_hit_yet > f64::EPSILON {
+        let chance = (c * n).min(1.0);
+        expected_draws += n * no_hit_yet * chance;
+        no_hit_yet *= 1.0 - chance;
+        n += 1.0;
+    }
+    1.0 / expected_draws
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calibrated_constants_give_the_nominal_rate() {
+        for p in [1e-4, 5e-4, 0.999e-3, 1e-3, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99] {
+            let rate = prd_rate(prd_constant(p));
+            assert!((rate - p).abs() < p * 1e-6, "p={p} rate={rate}");
+        }
+    }
+
+    #[test]
+    fn constants_match_the_known_values() {
+        assert!((prd_constant(0.1) - 0.014_746).abs() < 1e-6);
+        assert!((prd_constant(0.25) - 0.084_744).abs() < 1e-6);
+        assert!((prd_constant(0.5) - 0.302_103).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tiny_probabilities_are_calibrated_without_iterating() {
+        let c = prd_constant(1e-12);
+        assert!((c / (std::f64::consts::FRAC_PI_2 * 1e-24) - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,760 @@
+use {
+    crate::*,
+    rand::Rng,
+    std::sync::OnceLock,
+};
+
+/// A selector allowing to randomly select a value from a set of choices, each with an associated weight.
+///
+/// ```
+/// use rand_select::RandomSelector;
+/// let selector = RandomSelector::default()
+///    .with(1.0, 'A')
+///    .with(1.5, 'B')
+///    .with_none(3.0);
+/// let l = selector.select();
+/// // l has half a chance to be None, and is 50% more likely to be 'B' than 'A'
+/// ```
+///
+/// Weights are `f64` by default, but can be of any type implementing [Weight],
+/// for example `u32` for exact integer arithmetic.
+#[derive(Clone)]
+pub struct RandomSelector<T, W: Weight = f64> {
+    pub(crate) choices: Vec<Choice<T, W>>,
+    pub(crate) none_weight: NoneWeight<W::Total>,
+    pub(crate) strategy: Strategy,
+    pub(crate) none_policy: NonePolicy,
+    /// Lazily computed, and reset on any change of the weights other than
+    /// a push, so that rounding errors don't pile up
+    weights_sum: OnceLock<W::Total>,
+    /// Lazily built on first draw, reset on any change of the choices
+    index: OnceLock<Index<W>>,
+    /// The probabilities of the choices, lazily computed for [Self::select_entry],
+    /// and reset with the index
+    probabilities: OnceLock<Vec<f64>>,
+}
+
+impl<T, W: Weight> Default for RandomSelector<T, W> {
+    fn default() -> Self {
+        Self {
+            choices: Vec::new(),
+            none_weight: NoneWeight::default(),
+            strategy: Strategy::default(),
+            none_policy: NonePolicy::default(),
+            weights_sum: OnceLock::new(),
+            index: OnceLock::new(),
+            probabilities: OnceLock::new(),
+        }
+    }
+}
+
+impl<T, W: Weight> RandomSelector<T, W> {
+    /// Add a choice.
+    ///
+    /// A negative weight is taken as its absolute value. The weight isn't
+    /// otherwise checked: use [Self::try_with] if it may be NaN or infinite,
+    /// or if an integer total weight may overflow, in which case it saturates
+    /// at the maximum of its type.
+    pub fn with(mut self, weight: W, value: T) -> Self {
+        self.push(weight, value);
+        self
+    }
+    pub(crate) fn push(&mut self, weight: W, value: T) {
+        let weight = weight.abs();
+        self.choices.push(Choice { weight, value });
+        // adding at the end gives the same sum as summing all the weights again
+        if let Some(weights_sum) = self.weights_sum.get_mut() {
+            *weights_sum = weights_sum.saturating_add(weight.to_total());
+        }
+        self.reset_index();
+    }
+    /// Forget the sum of the weights and the index, after a change of the weights
+    fn weights_changed(&mut self) {
+        self.weights_sum.take();
+        self.reset_index();
+    }
+    /// Forget the index and the probabilities, after a change of the choices
+    fn reset_index(&mut self) {
+        self.index.take();
+        self.probabilities.take();
+    }
+    /// Check the weight, and that the total weight doesn't overflow when it
+    /// replaces the weight of the choice at `index`, or is added if None
+    pub(crate) fn check_weight(&self, weight: W, index: Option<usize>) -> Result<W, SelectorError> {
+        let weight = weight.check()?;
+        let mut weights_sum = self.weights_sum();
+        if let Some(index) = index {
+            weights_sum = weights_sum.sub_or_zero(self.choices[index].weight.to_total());
+        }
+        weights_sum
+            .checked_add(weight.to_total())
+            .and_then(|weights_sum| self.none_weight.checked_total(weights_sum))
+            .ok_or(SelectorError::WeightOverflow)?;
+        Ok(weight)
+    }
+    /// Add a choice, checking its weight is finite and not negative, and
+    /// that the total weight doesn't overflow.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let selector = RandomSelector::default()
+    ///    .try_with(1.0, 'A')?
+    ///    .try_with(1.5, 'B')?
+    ///    .try_with_none(3.0)?;
+    /// assert_eq!(
+    ///     selector.try_with(-1.0, 'C').err(),
+    ///     Some(SelectorError::NegativeWeight(-1.0)),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn try_with(self, weight: W, value: T) -> Result<Self, SelectorError> {
+        let weight = self.check_weight(weight, None)?;
+        Ok(self.with(weight, value))
+    }
+    /// Add a weight for which no value is selected.
+    ///
+    /// A negative weight is taken as its absolute value. An integer total
+    /// weight saturates at the maximum of its type instead of overflowing.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///     .with_none(u64::MAX)
+    ///     .with(1u32, 'A');
+    /// assert_eq!(selector.total_weight(), u64::MAX);
+    /// ```
+    pub fn with_none(mut self, weight: W::Total) -> Self {
+        self.none_weight = self.none_weight.add(weight.abs());
+        self.reset_index();
+        self
+    }
+    /// Add a weight for which no value is selected, checking it's finite
+    /// and not negative, and that the total weight doesn't overflow.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let selector = RandomSelector::default().try_with_none(u64::MAX)?;
+    /// assert_eq!(
+    ///     selector.try_with(1u32, 'A').err(),
+    ///     Some(SelectorError::WeightOverflow),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn try_with_none(self, weight: W::Total) -> Result<Self, SelectorError> {
+        let weight = weight.check()?;
+        self.none_weight
+            .checked_add(weight)
+            .and_then(|none_weight| none_weight.checked_total(self.weights_sum()))
+            .ok_or(SelectorError::WeightOverflow)?;
+        Ok(self.with_none(weight))
+    }
+    /// Complete choices to be None up to the given weight.
+    ///
+    /// This is convenient where all choices set are conventionnaly already normalized:
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(0.1, 'A')
+    ///    .with(0.2, 'B')
+    ///    .with_none_up_to(1.0);
+    /// ```
+    ///
+    /// The total is kept when the weights of the choices change.
+    ///
+    /// If the total is smaller than the sum of the weights, the last choices
+    /// can't be fully reached: use [Self::try_with_none_up_to] to prevent it.
+    pub fn with_none_up_to(mut self, total_weight: W::Total) -> Self {
+        self.none_weight = NoneWeight::UpTo(total_weight.abs());
+        self.reset_index();
+        self
+    }
+    /// Complete choices to be None up to the given weight, checking this total
+    /// is valid and not smaller than the sum of the weights of the choices.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError};
+    /// let selector = RandomSelector::default()
+    ///    .try_with(0.5, 'A')?
+    ///    .try_with(0.7, 'B')?;
+    /// assert_eq!(
+    ///     selector.try_with_none_up_to(1.0).err(),
+    ///     Some(SelectorError::TotalTooSmall { total: 1.0, weights_sum: 1.2 }),
+    /// );
+    /// # Ok::<(), SelectorError>(())
+    /// ```
+    pub fn try_with_none_up_to(self, total_weight: W::Total) -> Result<Self, SelectorError> {
+        let total_weight = check_total(total_weight, self.weights_sum())?;
+        Ok(self.with_none_up_to(total_weight))
+    }
+    /// Set the algorithm used to select values (default is `Strategy::Auto`).
+    ///
+    /// For a big table from which many values are drawn, the alias
+    /// method gives constant time selection:
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, Strategy};
+    /// let selector = (0..10_000)
+    ///     .fold(RandomSelector::default(), |s, i| s.with(i as f64, i))
+    ///     .with_none(1_000_000.0)
+    ///     .with_strategy(Strategy::Alias);
+    /// let mut rng = rand::rng();
+    /// for _ in 0..1000 {
+    ///     let _ = selector.select_with_rng(&mut rng);
+    /// }
+    /// ```
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self.reset_index();
+        self
+    }
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+    /// Set how None is handled when drawing several values (default is `NonePolicy::Keep`)
+    pub fn with_none_policy(mut self, none_policy: NonePolicy) -> Self {
+        self.none_policy = none_policy;
+        self
+    }
+    pub fn none_policy(&self) -> NonePolicy {
+        self.none_policy
+    }
+    /// Return the number of choices, not counting None
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+    /// Return the total weight, including the weight of None
+    pub fn total_weight(&self) -> W::Total {
+        self.none_weight.total(self.weights_sum())
+    }
+    /// Return the sum of the weights of the choices, not counting None
+    pub fn weights_sum(&self) -> W::Total {
+        *self.weights_sum.get_or_init(|| weights_sum(&self.choices))
+    }
+    /// Return the value of the choice at the given index
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.choices.get(index).map(|choice| &choice.value)
+    }
+    /// Return a mutable reference to the value of the choice at the given index
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.choices.get_mut(index).map(|choice| &mut choice.value)
+    }
+    /// Return the weight of the choice at the given index
+    pub fn weight(&self, index: usize) -> Option<W> {
+        self.choices.get(index).map(|choice| choice.weight)
+    }
+    /// Iterate over the weights and values of the choices
+    pub fn iter(&self) -> impl Iterator<Item = (W, &T)> {
+        self.choices.iter().map(|choice| (choice.weight, &choice.value))
+    }
+    /// Return the probability of the choice at the given index to be selected.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(1.5, 'B')
+    ///    .with_none(2.5);
+    /// assert_eq!(selector.probability_of(0), Some(0.2));
+    /// assert_eq!(selector.probability_of(1), Some(0.3));
+    /// assert_eq!(selector.probability_of(2), None);
+    /// assert_eq!(selector.none_probability(), 0.5);
+    /// ```
+    pub fn probability_of(&self, index: usize) -> Option<f64> {
+        let choice = self.choices.get(index)?;
+        let start = weights_sum(&self.choices[..index]);
+        Some(self.reachable_probability(start, choice.weight.to_total()))
+    }
+    /// Return the probability that no value is selected
+    pub fn none_probability(&self) -> f64 {
+        let total_weight = self.total_weight();
+        if total_weight == W::Total::ZERO {
+            return 1.0;
+        }
+        let none_weight = total_weight.sub_or_zero(self.weights_sum());
+        none_weight.to_f64() / total_weight.to_f64()
+    }
+    /// Iterate over the values of the choices, with their weights and probabilities
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(3.0, 'B');
+    /// let probabilities: Vec<(char, f64)> = selector
+    ///     .probabilities()
+    ///     .map(|(&l, _, p)| (l, p))
+    ///     .collect();
+    /// assert_eq!(probabilities, vec![('A', 0.25), ('B', 0.75)]);
+    /// ```
+    pub fn probabilities(&self) -> impl Iterator<Item = (&T, W, f64)> {
+        let mut start = W::Total::ZERO;
+        self.choices.iter().map(move |choice| {
+            let weight = choice.weight.to_total();
+            let probability = self.reachable_probability(start, weight);
+            start = start + weight;
+            (&choice.value, choice.weight, probability)
+        })
+    }
+    /// Compute the probability of a choice, knowing the sum of the weights
+    /// of the choices before it.
+    ///
+    /// The probability may be less than `weight / total_weight` when
+    /// the total is smaller than the sum of the weights.
+    fn reachable_probability(&self, start: W::Total, weight: W::Total) -> f64 {
+        let total_weight = self.total_weight();
+        if total_weight == W::Total::ZERO {
+            return 0.0;
+        }
+        let end = start + weight;
+        let reachable_weight = if end <= total_weight {
+            weight
+        } else {
+            total_weight.sub_or_zero(start)
+        };
+        reachable_weight.to_f64() / total_weight.to_f64()
+    }
+    /// Iterate over the weights and mutable values of the choices.
+    ///
+    /// Weights can be changed with [Self::set_weight] or [Self::retain].
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (W, &mut T)> {
+        self.choices.iter_mut().map(|choice| (choice.weight, &mut choice.value))
+    }
+    /// Change the weight of the choice at the given index.
+    ///
+    /// The weight of None is kept unchanged, the total weight changing
+    /// accordingly, unless the total was set with [Self::with_none_up_to].
+    /// A negative weight is taken as its absolute value.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let mut selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(1.5, 'B')
+    ///    .with_none(3.0);
+    /// selector.set_weight(0, 0.0);
+    /// assert_eq!(selector.total_weight(), 4.5);
+    /// assert!(selector.sample_iter(rand::rng()).take(100).all(|l| l != Some(&'A')));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_weight(&mut self, index: usize, weight: W) {
+        let weight = weight.abs();
+        self.choices[index].weight = weight;
+        self.weights_changed();
+    }
+    /// Change the weight of the choice at the given index, checking the
+    /// weight is finite and not negative, and that the total weight doesn't
+    /// overflow.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn try_set_weight(&mut self, index: usize, weight: W) -> Result<(), SelectorError> {
+        let weight = self.check_weight(weight, Some(index))?;
+        self.set_weight(index, weight);
+        Ok(())
+    }
+    /// Remove the choice at the given index, and return its value.
+    ///
+    /// The weight of None is kept unchanged, unless the total was set with
+    /// [Self::with_none_up_to].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let choice = self.choices.remove(index);
+        self.weights_changed();
+        choice.value
+    }
+    /// Keep only the choices for which the predicate, called with the value
+    /// and the weight, returns true.
+    ///
+    /// The weight of None is kept unchanged, unless the total was set with
+    /// [Self::with_none_up_to].
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let mut selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(1.5, 'B')
+    ///    .with(2.0, 'C')
+    ///    .with_none(3.0);
+    /// selector.retain(|&l, _| l != 'B');
+    /// assert_eq!(selector.len(), 2);
+    /// assert_eq!(selector.total_weight(), 6.0);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T, W) -> bool,
+    {
+        self.choices.retain(|choice| f(&choice.value, choice.weight));
+        self.weights_changed();
+    }
+    fn index(&self) -> &Index<W> {
+        self.index
+            .get_or_init(|| Index::new(self.strategy, &self.choices, self.total_weight()))
+    }
+    /// Select a random value among the provided ones.
+    ///
+    /// The thread local RNG is fetched on every call: when drawing many values,
+    /// prefer [Self::sample_iter] or [Self::select_n].
+    pub fn select(&self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.select_with_rng(&mut rng)
+    }
+    /// Select a random value among the provided ones, with the generator of your choice.
+    pub fn select_with_rng<R: Rng>(&self, mut r: R) -> Option<&T> {
+        self.select_index(&mut r).map(|idx| &self.choices[idx].value)
+    }
+    /// Iterate over the values which can be selected, with their reachable weights
+    fn reachable_choices(&self) -> impl Iterator<Item = (W::Total, &T)> {
+        reachable_weights(&self.choices, self.total_weight())
+            .zip(&self.choices)
+            .filter(|(weight, _)| *weight > W::Total::ZERO)
+            .map(|(weight, choice)| (weight, &choice.value))
+    }
+    /// Select a random value among the ones satisfying the predicate, without
+    /// building a new selector.
+    ///
+    /// The probabilities are renormalized over the matching values. With
+    /// `NonePolicy::Keep`, the None weight is kept and may be drawn. With
+    /// `NonePolicy::Skip`, None is returned only when no value matches.
+    ///
+    /// The predicate is called twice per value, so it can't have side effects.
+    ///
+    /// ```
+    /// use rand_select::{NonePolicy, RandomSelector};
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(2.0, 'B')
+    ///    .with(3.0, 'C')
+    ///    .with_none(6.0)
+    ///    .with_none_policy(NonePolicy::Skip);
+    /// let mut rng = rand::rng();
+    /// assert_eq!(selector.select_where(|&l| l == 'B', &mut rng), Some(&'B'));
+    /// assert_eq!(selector.select_where(|&l| l == 'Z', &mut rng), None);
+    /// ```
+    pub fn select_where<F, R>(&self, predicate: F, mut r: R) -> Option<&T>
+    where
+        F: Fn(&T) -> bool,
+        R: Rng,
+    {
+        let matching_weight = self
+            .reachable_choices()
+            .filter(|(_, value)| predicate(value))
+            .fold(W::Total::ZERO, |sum, (weight, _)| sum + weight);
+        let limit = match self.none_policy {
+            NonePolicy::Keep => {
+                matching_weight + self.total_weight().sub_or_zero(self.weights_sum())
+            }
+            NonePolicy::Skip => matching_weight,
+        };
+        if limit <= W::Total::ZERO {
+            return None;
+        }
+        let random_value = limit.random_below(&mut r);
+        let mut end = W::Total::ZERO;
+        let mut last = None;
+        let matching = self
+            .reachable_choices()
+            .filter(|(_, value)| predicate(value));
+        for (weight, value) in matching {
+            end = end + weight;
+            if random_value < end {
+                return Some(value);
+            }
+            last = Some(value);
+        }
+        // the matching weights are summed again in the same order, so the
+        // draw can only reach their end by a rounding of the generator
+        match self.none_policy {
+            NonePolicy::Skip if random_value <= end => last,
+            _ => None,
+        }
+    }
+    /// Select a random value with the generator of your choice, consuming the selector.
+    pub fn into_select<R: Rng>(mut self, mut r: R) -> Option<T> {
+        self.select_index(&mut r)
+            .map(|idx| self.choices.swap_remove(idx).value)
+    }
+    /// Select a random value with the generator of your choice, and remove its choice
+    /// from the selector.
+    ///
+    /// The weight of None is kept unchanged, so that this can be used to draw
+    /// from a bag:
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let mut bag = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(2.0, 'B')
+    ///    .with(3.0, 'C');
+    /// let mut rng = rand::rng();
+    /// let mut drawn = Vec::new();
+    /// while let Some(l) = bag.take_random(&mut rng) {
+    ///     drawn.push(l);
+    /// }
+    /// drawn.sort();
+    /// assert_eq!(drawn, vec!['A', 'B', 'C']);
+    /// ```
+    pub fn take_random<R: Rng>(&mut self, mut r: R) -> Option<T> {
+        self.select_index(&mut r)
+            .map(|idx| self.remove(idx))
+    }
+    /// Select a random choice, and return its index, weight and probability
+    /// along its value.
+    pub fn select_entry(&self) -> Option<Selected<'_, T, W>> {
+        let mut rng = rand::rng();
+        self.select_entry_with_rng(&mut rng)
+    }
+    /// Select a random choice with the generator of your choice, and return its
+    /// index, weight and probability along its value.
+    ///
+    /// This is useful when values are repeated or to log the probability.
+    /// The probabilities of all choices are computed on the first call, and
+    /// kept until the choices change.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, "common")
+    ///    .with(3.0, "common");
+    /// let selected = selector.select_entry_with_rng(rand::rng()).unwrap();
+    /// assert_eq!(selected.value, &"common");
+    /// if selected.index == 0 {
+    ///     assert_eq!(selected.probability, 0.25);
+    /// } else {
+    ///     assert_eq!(selected.probability, 0.75);
+    /// }
+    /// ```
+    ///
+    /// The probability is the real one, even when the total is smaller
+    /// than the sum of the weights:
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(0.5, 'A')
+    ///    .with(0.7, 'B')
+    ///    .with_none_up_to(1.0);
+    /// let selected = selector.select_entry_with_rng(rand::rng()).unwrap();
+    /// assert_eq!(selected.weight, if selected.index == 0 { 0.5 } else { 0.7 });
+    /// assert_eq!(selected.probability, 0.5);
+    /// ```
+    pub fn select_entry_with_rng<R: Rng>(&self, mut r: R) -> Option<Selected<'_, T, W>> {
+        self.select_index(&mut r).map(|index| {
+            let choice = &self.choices[index];
+            let probabilities = self.probabilities.get_or_init(|| {
+                self.probabilities()
+                    .map(|(_, _, probability)| probability)
+                    .collect()
+            });
+            Selected {
+                index,
+                weight: choice.weight,
+                probability: probabilities[index],
+                value: &choice.value,
+            }
+        })
+    }
+    /// Return an infinite iterator of values selected with the given RNG.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(1.5, 'B')
+    ///    .with_none(3.0);
+    /// let a_count = selector
+    ///     .sample_iter(rand::rng())
+    ///     .take(1000)
+    ///     .filter(|&l| l == Some(&'A'))
+    ///     .count();
+    /// assert!(a_count < 1000);
+    /// ```
+    pub fn sample_iter<R: Rng>(&self, r: R) -> SampleIter<'_, T, R, W> {
+        SampleIter::new(self, r)
+    }
+    /// Select `n` values, with replacement.
+    ///
+    /// ```
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(1.5, 'B');
+    /// let values = selector.select_n(10, rand::rng());
+    /// assert_eq!(values.len(), 10);
+    /// assert!(values.iter().all(|l| l.is_some()));
+    /// ```
+    pub fn select_n<R: Rng>(&self, n: usize, r: R) -> Vec<Option<&T>> {
+        self.sample_iter(r).take(n).collect()
+    }
+    /// Select the index of a random choice
+    pub(crate) fn select_index<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<usize> {
+        let total_weight = self.total_weight();
+        if total_weight == W::Total::ZERO {
+            return None;
+        }
+        self.index()
+            .select(&self.choices, total_weight, rng)
+    }
+    /// Select the index of a random choice, drawing a weight below the given
+    /// limit, which allows excluding None when the limit is the sum of the weights
+    pub(crate) fn select_index_below<R: Rng + ?Sized>(
+        &self,
+        limit: W::Total,
+        rng: &mut R,
+    ) -> Option<usize> {
+        if limit <= W::Total::ZERO {
+            return None;
+        }
+        let random_value = limit.random_below(rng);
+        self.index().locate(&self.choices, random_value)
+    }
+    /// Select up to `k` distinct values, without replacement, in draw order.
+    ///
+    /// Each value is drawn with a probability proportional to its weight among
+    /// the values not drawn yet.
+    /// With `NonePolicy::Keep`, None takes part in the draw, and drawing it
+    /// stops the draw. With `NonePolicy::Skip`, None is ignored.
+    ///
+    /// Fewer than `k` values are returned when there are not enough choices
+    /// with a positive weight, or when None is drawn.
+    ///
+    /// ```
+    /// use rand_select::{NonePolicy, RandomSelector};
+    /// let selector = RandomSelector::default()
+    ///    .with(1.0, 'A')
+    ///    .with(2.0, 'B')
+    ///    .with(3.0, 'C')
+    ///    .with(4.0, 'D')
+    ///    .with_none(5.0)
+    ///    .with_none_policy(NonePolicy::Skip);
+    /// let values = selector.select_many_distinct(3, rand::rng());
+    /// assert_eq!(values.len(), 3);
+    /// assert!(values[0] != values[1] && values[1] != values[2] && values[0] != values[2]);
+    /// ```
+    pub fn select_many_distinct<R: Rng>(&self, k: usize, mut r: R) -> Vec<&T> {
+        if k == 0 {
+            return Vec::new();
+        }
+        // Efraimidis-Spirakis: each candidate gets the key u^(1/w), with u uniform
+        // in (0, 1], and the candidates with the greatest keys are drawn in order.
+        // Keys are compared in log space for precision with small weights.
+        let total_weight = self.total_weight();
+        let mut weights: Vec<f64> = reachable_weights(&self.choices, total_weight)
+            .map(Total::to_f64)
+            .collect();
+        if self.none_policy == NonePolicy::Keep {
+            let none_weight = total_weight.sub_or_zero(self.weights_sum());
+            weights.push(none_weight.to_f64());
+        }
+        let mut keys: Vec<(f64, usize)> = weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0.0)
+            .map(|(idx, weight)| {
+                let u = 1.0 - r.random::<f64>();
+                (u.ln() / weight, idx)
+            })
+            .collect();
+        let by_key_desc = |a: &(f64, usize), b: &(f64, usize)| b.0.total_cmp(&a.0);
+        if k < keys.len() {
+            keys.select_nth_unstable_by(k - 1, by_key_desc);
+            keys.truncate(k);
+        }
+        keys.sort_unstable_by(by_key_desc);
+        keys.iter()
+            .map_while(|&(_, idx)| self.choices.get(idx).map(|choice| &choice.value))
+            .collect()
+    }
+}
+
+impl<T: Clone, W: Weight> RandomSelector<T, W> {
+    /// Select a random value among the provided ones, and return a clone of it.
+    pub fn select_cloned(&self) -> Option<T> {
+        self.select().cloned()
+    }
+    /// Select a random value with the generator of your choice, and return a clone of it.
+    pub fn select_cloned_with_rng<R: Rng>(&self, r: R) -> Option<T> {
+        self.select_with_rng(r).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reweighting_doesnt_create_a_none_weight() {
+        let mut selector = RandomSelector::default()
+            .with(0.1, 'A')
+            .with(0.2, 'B');
+        for i in 0..1_000_000 {
+            selector.set_weight(i % 2, (i % 1000) as f64 * 0.001 + 0.1);
+        }
+        assert_eq!(selector.total_weight(), selector.weights_sum());
+        assert_eq!(selector.none_probability(), 0.0);
+        selector.remove(0);
+        selector.retain(|_, weight| weight > 0.0);
+        assert_eq!(selector.none_probability(), 0.0);
+    }
+
+    #[test]
+    fn none_weight_is_kept_on_changes() {
+        let mut selector = RandomSelector::default()
+            .with(1.0, 'A')
+            .with(3.0, 'B')
+            .with_none(4.0);
+        selector.set_weight(1, 1.0);
+        assert_eq!(selector.total_weight(), 6.0);
+        selector.remove(0);
+        assert_eq!(selector.total_weight(), 5.0);
+        let mut selector = selector.with_none_up_to(10.0);
+        selector.set_weight(0, 2.0);
+        selector.push(3.0, 'C');
+        assert_eq!(selector.total_weight(), 10.0);
+        assert_eq!(selector.none_probability(), 0.5);
+    }
+
+    #[test]
+    fn select_where_skip_draws_only_matching_values() {
+        let selector = RandomSelector::default()
+            .with(0.1, 'A')
+            .with(0.2, 'B')
+            .with(0.3, 'C')
+            .with(0.7, 'D')
+            .with_none(5.0)
+            .with_none_policy(NonePolicy::Skip);
+        let mut rng = rand::rng();
+        for _ in 0..10_000 {
+            let value = selector.select_where(|&l| l != 'B', &mut rng);
+            assert!(matches!(value, Some('A' | 'C' | 'D')), "{value:?}");
+        }
+        assert_eq!(selector.select_where(|_| false, &mut rng), None);
+    }
+
+    #[test]
+    fn overflowing_total_is_checked_or_saturates() {
+        let mut selector = RandomSelector::default()
+            .with(1u32, 'A')
+            .with(2, 'B')
+            .with_none(u64::MAX - 4);
+        assert_eq!(selector.try_set_weight(0, 2), Ok(()));
+        assert_eq!(selector.try_set_weight(0, 3), Err(SelectorError::WeightOverflow));
+        assert_eq!(selector.weight(0), Some(2));
+        assert_eq!(selector.clone().try_with_none(1).err(), Some(SelectorError::WeightOverflow));
+        assert_eq!(selector.try_extend([(1, 'C')]), Err(SelectorError::WeightOverflow));
+        selector.set_weight(1, u32::MAX);
+        assert_eq!(selector.total_weight(), u64::MAX);
+        assert!(selector.none_probability() < 1.0);
+        selector.select_with_rng(rand::rng());
+    }
+}
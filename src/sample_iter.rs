@@ -0,0 +1,28 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// An infinite iterator of values randomly selected by a [RandomSelector].
+///
+/// Built with [RandomSelector::sample_iter].
+pub struct SampleIter<'s, T, R, W: Weight = f64> {
+    selector: &'s RandomSelector<T, W>,
+    rng: R,
+}
+
+impl<'s, T, R: Rng, W: Weight> SampleIter<'s, T, R, W> {
+    pub(crate) fn new(selector: &'s RandomSelector<T, W>, rng: R) -> Self {
+        Self { selector, rng }
+    }
+}
+
+impl<'s, T, R: Rng, W: Weight> Iterator for SampleIter<'s, T, R, W> {
+    type Item = Option<&'s T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.selector.select_with_rng(&mut self.rng))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
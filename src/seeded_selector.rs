@@ -0,0 +1,95 @@
+use {
+    crate::*,
+    rand::{
+        Rng,
+        SeedableRng,
+    },
+};
+
+/// A [RandomSelector] owning a seedable RNG, so that a sequence of draws can
+/// be reproduced.
+///
+/// The RNG can be snapshot then restored to replay draws.
+///
+/// With the `chacha` feature, `ChaChaSelector` uses ChaCha8, whose streams
+/// are the same on all platforms. For draws to be the same across versions
+/// of this crate and of `rand`, use [Strategy::StableV1] too.
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand_select::{RandomSelector, SeededSelector};
+/// let mut selector: SeededSelector<char, StdRng> = SeededSelector::new(
+///     RandomSelector::default()
+///         .with(1.0, 'A')
+///         .with(1.5, 'B')
+///         .with_none(3.0),
+///     42,
+/// );
+/// let snapshot = selector.snapshot();
+/// let first: Vec<Option<char>> = (0..10).map(|_| selector.select().copied()).collect();
+/// selector.restore(snapshot);
+/// let second: Vec<Option<char>> = (0..10).map(|_| selector.select().copied()).collect();
+/// assert_eq!(first, second);
+/// ```
+#[derive(Clone)]
+pub struct SeededSelector<T, R, W: Weight = f64> {
+    selector: RandomSelector<T, W>,
+    rng: R,
+}
+
+/// A [SeededSelector] using the ChaCha8 RNG
+///
+/// ```
+/// use rand_select::{ChaChaSelector, RandomSelector};
+/// let selector = RandomSelector::default()
+///     .with(1.0, 'A')
+///     .with(1.5, 'B');
+/// let mut a = ChaChaSelector::new(selector.clone(), 7);
+/// let mut b = ChaChaSelector::new(selector, 7);
+/// assert_eq!(a.select_n(20), b.select_n(20));
+/// ```
+#[cfg(feature = "chacha")]
+pub type ChaChaSelector<T, W = f64> = SeededSelector<T, rand_chacha::ChaCha8Rng, W>;
+
+impl<T, R: Rng + SeedableRng, W: Weight> SeededSelector<T, R, W> {
+    /// Build a selector whose RNG is seeded with the given seed
+    pub fn new(selector: RandomSelector<T, W>, seed: u64) -> Self {
+        Self::with_rng(selector, R::seed_from_u64(seed))
+    }
+    /// Reseed the RNG, restarting its stream
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = R::seed_from_u64(seed);
+    }
+}
+
+impl<T, R: Rng, W: Weight> SeededSelector<T, R, W> {
+    /// Build a selector with an already initialized RNG
+    pub fn with_rng(selector: RandomSelector<T, W>, rng: R) -> Self {
+        Self { selector, rng }
+    }
+    pub fn selector(&self) -> &RandomSelector<T, W> {
+        &self.selector
+    }
+    pub fn rng(&self) -> &R {
+        &self.rng
+    }
+    /// Select a random value, advancing the RNG
+    pub fn select(&mut self) -> Option<&T> {
+        self.selector.select_with_rng(&mut self.rng)
+    }
+    /// Select `n` values, with replacement, advancing the RNG
+    pub fn select_n(&mut self, n: usize) -> Vec<Option<&T>> {
+        self.selector.select_n(n, &mut self.rng)
+    }
+}
+
+impl<T, R: Rng + Clone, W: Weight> SeededSelector<T, R, W> {
+    /// Return the state of the RNG, to be given to [Self::restore] to replay draws
+    pub fn snapshot(&self) -> R {
+        self.rng.clone()
+    }
+    /// Restore a state of the RNG obtained with [Self::snapshot]
+    pub fn restore(&mut self, snapshot: R) {
+        self.rng = snapshot;
+    }
+}
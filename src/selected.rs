@@ -0,0 +1,12 @@
+/// A choice selected by a [RandomSelector](crate::RandomSelector), with
+/// the information needed to know which one it is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Selected<'s, T, W = f64> {
+    /// The index of the choice, in insertion order
+    pub index: usize,
+    /// The weight of the choice
+    pub weight: W,
+    /// The probability the choice had to be selected
+    pub probability: f64,
+    pub value: &'s T,
+}
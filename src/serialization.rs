@@ -0,0 +1,126 @@
+use {
+    crate::*,
+    serde::{
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+        de,
+    },
+};
+
+/// The serialized form of a RandomSelector.
+///
+/// The weight of None is given either as `none`, added to the weights of the
+/// choices, or as `none_up_to`, the total weight. A `none_up_to` smaller than
+/// the sum of the weights must be acknowledged with `truncated`.
+#[derive(Serialize)]
+#[serde(bound = "T: Serialize, W: Serialize, W::Total: Serialize")]
+struct SelectorRef<'s, T, W: Weight> {
+    choices: &'s [Choice<T, W>],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    none: Option<W::Total>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    none_up_to: Option<W::Total>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    truncated: bool,
+    strategy: Strategy,
+    none_policy: NonePolicy,
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "T: Deserialize<'de>, W: Deserialize<'de>, W::Total: Deserialize<'de>")]
+struct SelectorDef<T, W: Weight> {
+    choices: Vec<Choice<T, W>>,
+    #[serde(default)]
+    none: Option<W::Total>,
+    #[serde(default)]
+    none_up_to: Option<W::Total>,
+    #[serde(default)]
+    truncated: bool,
+    #[serde(default)]
+    strategy: Strategy,
+    #[serde(default)]
+    none_policy: NonePolicy,
+}
+
+impl<T, W> Serialize for RandomSelector<T, W>
+where
+    T: Serialize,
+    W: Weight + Serialize,
+    W::Total: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (none, none_up_to) = match self.none_weight {
+            NoneWeight::Added(none) if none == W::Total::ZERO => (None, None),
+            NoneWeight::Added(none) => (Some(none), None),
+            NoneWeight::UpTo(total_weight) => (None, Some(total_weight)),
+        };
+        let truncated = none_up_to.is_some_and(|total| total.is_below_sum(self.weights_sum()));
+        SelectorRef {
+            choices: &self.choices,
+            none,
+            none_up_to,
+            truncated,
+            strategy: self.strategy,
+            none_policy: self.none_policy,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// The weights and the weight of None are checked to be finite and not negative,
+/// and `none_up_to` not to be smaller than the sum of the weights, unless
+/// `truncated` is set, as it is when serializing such a selector:
+///
+/// ```
+/// use rand_select::RandomSelector;
+/// let selector = RandomSelector::default()
+///    .with(0.5, 'A')
+///    .with(0.7, 'B')
+///    .with_none_up_to(1.0);
+/// let json = serde_json::to_string(&selector).unwrap();
+/// assert!(json.contains(r#""none_up_to":1.0,"truncated":true"#));
+/// let selector: RandomSelector<char> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(selector.total_weight(), 1.0);
+/// assert_eq!(selector.probability_of(1), Some(0.5));
+/// let error = serde_json::from_str::<RandomSelector<char>>(r#"{
+///     "choices": [
+///         { "weight": 0.5, "value": "A" },
+///         { "weight": 0.7, "value": "B" }
+///     ],
+///     "none_up_to": 1.0
+/// }"#).err().unwrap();
+/// assert!(error.to_string().starts_with("total weight 1 is smaller"));
+/// ```
+impl<'de, T, W> Deserialize<'de> for RandomSelector<T, W>
+where
+    T: Deserialize<'de>,
+    W: Weight + Deserialize<'de>,
+    W::Total: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let def = SelectorDef::<T, W>::deserialize(deserializer)?;
+        let mut selector = RandomSelector::default()
+            .with_strategy(def.strategy)
+            .with_none_policy(def.none_policy);
+        for choice in def.choices {
+            selector = selector
+                .try_with(choice.weight, choice.value)
+                .map_err(de::Error::custom)?;
+        }
+        selector = match (def.none, def.none_up_to) {
+            (Some(_), Some(_)) => {
+                return Err(de::Error::custom("none and none_up_to are exclusive"));
+            }
+            (Some(none), None) => selector.try_with_none(none),
+            (None, Some(none_up_to)) if def.truncated => {
+                none_up_to.check().map(|total| selector.with_none_up_to(total))
+            }
+            (None, Some(none_up_to)) => selector.try_with_none_up_to(none_up_to),
+            (None, None) => Ok(selector),
+        }
+        .map_err(de::Error::custom)?;
+        Ok(selector)
+    }
+}
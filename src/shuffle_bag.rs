@@ -0,0 +1,270 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// When a [ShuffleBag] is refilled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefillPolicy {
+    /// Refill the bag when it's empty, so that every item is handed out before
+    /// any is handed out again
+    #[default]
+    WhenEmpty,
+    /// Refill the bag when there are no more than this number of items left,
+    /// which makes the end of a bag less predictable
+    Threshold(usize),
+    /// Never refill the bag automatically: draws return None when it's empty
+    /// (see [ShuffleBag::refill])
+    Never,
+}
+
+/// A bag from which items are drawn without replacement, to prevent streaks.
+///
+/// It's built from a [RandomSelector], the weight of each choice being the
+/// number of its copies in the bag, and the weight of None the number of None
+/// in the bag. When the total set with `with_none_up_to` is smaller than the
+/// sum of the weights, the last choices only get the copies within the total.
+///
+/// ```
+/// use rand_select::{RandomSelector, ShuffleBag};
+/// let mut bag = ShuffleBag::new(
+///     RandomSelector::default()
+///         .with(1.0, "rare")
+///         .with(3.0, "common")
+/// )?;
+/// let mut rng = rand::rng();
+/// let mut drawn: Vec<&str> = (0..4)
+///     .filter_map(|_| bag.draw_with_rng(&mut rng).copied())
+///     .collect();
+/// drawn.sort();
+/// assert_eq!(drawn, vec!["common", "common", "common", "rare"]);
+/// assert!(bag.is_empty());
+/// # Ok::<(), rand_select::SelectorError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShuffleBag<T> {
+    values: Vec<T>,
+    /// Number of copies of each value in a full bag
+    counts: Vec<usize>,
+    none_count: usize,
+    /// Number of copies of each value still in the bag
+    remaining: Vec<usize>,
+    none_remaining: usize,
+    refill_policy: RefillPolicy,
+}
+
+impl<T, W: Weight> TryFrom<RandomSelector<T, W>> for ShuffleBag<T> {
+    type Error = SelectorError;
+    fn try_from(selector: RandomSelector<T, W>) -> Result<Self, Self::Error> {
+        Self::new(selector)
+    }
+}
+
+/// Above this number, not all integers are exactly represented by a f64
+const MAX_EXACT_COUNT: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+
+/// Return the number of items a weight stands for
+fn count<S: Total>(weight: S) -> Result<usize, SelectorError> {
+    let weight = weight.check()?.to_f64();
+    if weight.fract() != 0.0 {
+        return Err(SelectorError::NotACount(weight));
+    }
+    if weight > MAX_EXACT_COUNT || weight > usize::MAX as f64 {
+        return Err(SelectorError::CountOverflow);
+    }
+    Ok(weight as usize)
+}
+
+impl<T> ShuffleBag<T> {
+    /// Build a full bag from the choices of the selector.
+    ///
+    /// Return an error if a weight isn't an integer, or if the bag would
+    /// hold more items than can be exactly counted.
+    ///
+    /// ```
+    /// use rand_select::{RandomSelector, SelectorError, ShuffleBag};
+    /// let normalized = RandomSelector::default()
+    ///     .with(0.25, 'a')
+    ///     .with(0.25, 'b')
+    ///     .with_none_up_to(1.0);
+    /// assert_eq!(
+    ///     ShuffleBag::new(normalized).err(),
+    ///     Some(SelectorError::NotACount(0.25)),
+    /// );
+    /// let huge = RandomSelector::default()
+    ///     .with(1e30, 'a')
+    ///     .with(1.0, 'b');
+    /// assert_eq!(ShuffleBag::new(huge).err(), Some(SelectorError::CountOverflow));
+    /// ```
+    pub fn new<W: Weight>(selector: RandomSelector<T, W>) -> Result<Self, SelectorError> {
+        let total_weight = selector.total_weight();
+        let none_weight = total_weight.sub_or_zero(selector.weights_sum());
+        // when the total is smaller than the sum of the weights, the last
+        // choices only get the copies they could be drawn as
+        let weights: Vec<W::Total> = reachable_weights(&selector.choices, total_weight).collect();
+        let mut counts = Vec::with_capacity(selector.len());
+        let mut values = Vec::with_capacity(selector.len());
+        let mut total_count: usize = 0;
+        for (choice, weight) in selector.choices.into_iter().zip(weights) {
+            let count = count(weight)?;
+            total_count = total_count
+                .checked_add(count)
+                .ok_or(SelectorError::CountOverflow)?;
+            counts.push(count);
+            values.push(choice.value);
+        }
+        let none_count = count(none_weight)?;
+        total_count
+            .checked_add(none_count)
+            .ok_or(SelectorError::CountOverflow)?;
+        Ok(Self {
+            values,
+            remaining: counts.clone(),
+            counts,
+            none_count,
+            none_remaining: none_count,
+            refill_policy: RefillPolicy::default(),
+        })
+    }
+    /// Set when the bag is refilled (default is `RefillPolicy::WhenEmpty`)
+    pub fn with_refill_policy(mut self, refill_policy: RefillPolicy) -> Self {
+        self.refill_policy = refill_policy;
+        self
+    }
+    pub fn refill_policy(&self) -> RefillPolicy {
+        self.refill_policy
+    }
+    /// Add the content of a full bag to the remaining items
+    pub fn refill(&mut self) {
+        for (remaining, count) in self.remaining.iter_mut().zip(&self.counts) {
+            *remaining += count;
+        }
+        self.none_remaining += self.none_count;
+    }
+    /// Return the number of items (including None) left in the bag
+    pub fn remaining_len(&self) -> usize {
+        self.remaining.iter().sum::<usize>() + self.none_remaining
+    }
+    pub fn is_empty(&self) -> bool {
+        self.remaining_len() == 0
+    }
+    /// Iterate over the values, with the number of their copies left in the bag
+    pub fn remaining(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.values.iter().zip(self.remaining.iter().copied())
+    }
+    /// Return the number of None left in the bag
+    pub fn remaining_none(&self) -> usize {
+        self.none_remaining
+    }
+    /// Draw an item from the bag
+    pub fn draw(&mut self) -> Option<&T> {
+        let mut rng = rand::rng();
+        self.draw_with_rng(&mut rng)
+    }
+    /// Draw an item from the bag, with the generator of your choice.
+    ///
+    /// The bag is refilled before the draw if the refill policy requires it.
+    pub fn draw_with_rng<R: Rng>(&mut self, mut r: R) -> Option<&T> {
+        let mut remaining_len = self.remaining_len();
+        let must_refill = match self.refill_policy {
+            RefillPolicy::WhenEmpty => remaining_len == 0,
+            RefillPolicy::Threshold(threshold) => remaining_len <= threshold,
+            RefillPolicy::Never => false,
+        };
+        if must_refill {
+            self.refill();
+            remaining_len = self.remaining_len();
+        }
+        if remaining_len == 0 {
+            return None;
+        }
+        let mut random_value = r.random_range(0..remaining_len);
+        for (idx, remaining) in self.remaining.iter_mut().enumerate() {
+            if random_value < *remaining {
+                *remaining -= 1;
+                return Some(&self.values[idx]);
+            }
+            random_value -= *remaining;
+        }
+        self.none_remaining -= 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        rand::SeedableRng,
+        rand_chacha::ChaCha8Rng,
+    };
+
+    fn bag() -> ShuffleBag<char> {
+        ShuffleBag::new(
+            RandomSelector::default()
+                .with(1.0, 'A')
+                .with(2.0, 'B')
+                .with_none(1.0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn truncated_choices_get_their_reachable_count() {
+        let bag = ShuffleBag::new(
+            RandomSelector::default()
+                .with(2u32, 'A')
+                .with(3, 'B')
+                .with(4, 'C')
+                .with_none_up_to(4),
+        )
+        .unwrap();
+        let counts: Vec<(char, usize)> = bag.remaining().map(|(&l, n)| (l, n)).collect();
+        assert_eq!(counts, vec![('A', 2), ('B', 2), ('C', 0)]);
+        assert_eq!(bag.remaining_none(), 0);
+    }
+
+    #[test]
+    fn never_refilled_bag_returns_none_when_empty() {
+        let mut bag = bag().with_refill_policy(RefillPolicy::Never);
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut drawn: Vec<Option<char>> = (0..4)
+            .map(|_| bag.draw_with_rng(&mut rng).copied())
+            .collect();
+        drawn.sort();
+        assert_eq!(drawn, vec![None, Some('A'), Some('B'), Some('B')]);
+        assert!(bag.is_empty());
+        for _ in 0..10 {
+            assert_eq!(bag.draw_with_rng(&mut rng), None);
+        }
+        assert!(bag.is_empty());
+        bag.refill();
+        assert_eq!(bag.remaining_len(), 4);
+        assert_eq!(bag.remaining_none(), 1);
+    }
+
+    #[test]
+    fn refill_adds_a_full_bag() {
+        let mut bag = bag();
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        bag.draw_with_rng(&mut rng);
+        bag.refill();
+        assert_eq!(bag.remaining_len(), 7);
+        let copies: usize = bag.remaining().map(|(_, n)| n).sum();
+        assert_eq!(copies + bag.remaining_none(), 7);
+    }
+
+    #[test]
+    fn threshold_refills_before_the_bag_is_empty() {
+        let mut bag = bag().with_refill_policy(RefillPolicy::Threshold(1));
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        // 4 items: draws leave 3, then 2, then 1
+        for remaining_len in [3, 2, 1] {
+            bag.draw_with_rng(&mut rng);
+            assert_eq!(bag.remaining_len(), remaining_len);
+        }
+        // with 1 item left, the bag is refilled before the draw
+        bag.draw_with_rng(&mut rng);
+        assert_eq!(bag.remaining_len(), 4);
+    }
+}
@@ -0,0 +1,78 @@
+/// The algorithm used by a [RandomSelector](crate::RandomSelector) to pick a choice.
+///
+/// All strategies give the same distribution, but `Alias` and `StableV1` don't
+/// consume the RNG in the same way as the other ones, so a seeded RNG doesn't give
+/// the same sequence of values with all strategies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Strategy {
+    /// `Linear` for small tables, `Cumulative` for bigger ones.
+    ///
+    /// As both give the same values for the same RNG, this is transparent.
+    #[default]
+    Auto,
+    /// Walk the choices until the drawn weight is reached.
+    ///
+    /// There's no preparation, but each draw is O(n).
+    Linear,
+    /// Binary search in the cumulated weights.
+    ///
+    /// An array of cumulated weights is built in O(n) on first draw, then each
+    /// draw is O(log n).
+    Cumulative,
+    /// Vose's alias method.
+    ///
+    /// A table is built in O(n) on first draw, then each draw is O(1).
+    /// This is the best choice for big tables from which many values are drawn.
+    /// When the total weight multiplied by the number of choices overflows,
+    /// `Cumulative` is used instead.
+    Alias,
+    /// A documented algorithm, giving for a given RNG stream the same values on
+    /// all platforms and in all versions of this crate having this strategy,
+    /// whatever the version of `rand`.
+    ///
+    /// Its draws are O(log n). The algorithm is:
+    /// - the weights are cumulated in insertion order, in their total type (`f64`
+    ///   for `f64` weights), then converted to `f64`
+    /// - each cumulated weight `c` is converted to a 53 bits fixed-point bound,
+    ///   `floor(min(c, total) / total * 2^53)`
+    /// - a draw takes one `u64` from the RNG with `next_u64`, and keeps its 53
+    ///   high bits `u`
+    /// - the selected choice is the first one whose bound is greater than `u`,
+    ///   or None if there's none.
+    ///
+    /// Only the selection of a single value (`select_with_rng`, `select_entry_with_rng`,
+    /// `sample_iter`, `select_n`, `take_random`, etc.) is covered by this guarantee.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use rand_select::{RandomSelector, Strategy};
+    /// let selector = RandomSelector::default()
+    ///     .with(1.0, 'A')
+    ///     .with(2.0, 'B')
+    ///     .with(3.0, 'C')
+    ///     .with_none(4.0)
+    ///     .with_strategy(Strategy::StableV1);
+    /// let draws: String = selector
+    ///     .sample_iter(ChaCha8Rng::seed_from_u64(42))
+    ///     .take(30)
+    ///     .map(|l| l.copied().unwrap_or('-'))
+    ///     .collect();
+    /// assert_eq!(draws, "--C-BBC--BC--C-BCBCABC-AAB----");
+    ///
+    /// let selector = RandomSelector::default()
+    ///     .with(0.1, 'A')
+    ///     .with(0.2, 'B')
+    ///     .with_none_up_to(1.0)
+    ///     .with_strategy(Strategy::StableV1);
+    /// let draws: String = selector
+    ///     .sample_iter(ChaCha8Rng::seed_from_u64(2024))
+    ///     .take(30)
+    ///     .map(|l| l.copied().unwrap_or('-'))
+    ///     .collect();
+    /// assert_eq!(draws, "B-----A-------------B----B-B--");
+    /// ```
+    StableV1,
+}
@@ -0,0 +1,248 @@
+use {
+    crate::*,
+    rand::Rng,
+};
+
+/// The result of goodness-of-fit tests comparing the values drawn from a
+/// selector with their expected probabilities.
+///
+/// The p-values are the probabilities, for a fair selector, to get a deviation
+/// at least as big as the observed one: a very small p-value means a bias.
+/// For the tests to be meaningful, there should be at least 5 expected draws
+/// of each possible outcome.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FairnessReport {
+    pub samples: usize,
+    /// The number of possible outcomes minus one
+    pub degrees_of_freedom: usize,
+    /// Pearson's chi-squared statistic
+    pub chi_squared: f64,
+    pub chi_squared_p_value: f64,
+    /// The G statistic of the likelihood-ratio test
+    pub g: f64,
+    pub g_p_value: f64,
+}
+
+impl FairnessReport {
+    /// Run the tests on counts of outcomes, knowing their expected probabilities.
+    ///
+    /// An outcome with a zero probability which was observed makes the
+    /// statistics infinite and the p-values zero.
+    ///
+    /// ```
+    /// use rand_select::FairnessReport;
+    /// let report = FairnessReport::from_counts(&[620, 380], &[0.5, 0.5]);
+    /// assert!(!report.is_fair(0.001));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices don't have the same length.
+    pub fn from_counts(counts: &[usize], probabilities: &[f64]) -> Self {
+        assert_eq!(
+            counts.len(),
+            probabilities.len(),
+            "there must be one probability per count",
+        );
+        let samples: usize = counts.iter().sum();
+        let mut outcomes = 0;
+        let mut chi_squared = 0.0;
+        let mut g = 0.0;
+        for (&count, &probability) in counts.iter().zip(probabilities) {
+            let observed = count as f64;
+            let expected = samples as f64 * probability;
+            if expected > 0.0 {
+                outcomes += 1;
+                chi_squared += (observed - expected).powi(2) / expected;
+                if count > 0 {
+                    g += 2.0 * observed * (observed / expected).ln();
+                }
+            } else if count > 0 {
+                chi_squared = f64::INFINITY;
+                g = f64::INFINITY;
+            }
+        }
+        let degrees_of_freedom = outcomes.max(1) - 1;
+        Self {
+            samples,
+            degrees_of_freedom,
+            chi_squared,
+            chi_squared_p_value: chi_squared_p_value(chi_squared, degrees_of_freedom),
+            g,
+            g_p_value: chi_squared_p_value(g, degrees_of_freedom),
+        }
+    }
+    /// Tell whether both tests accept the hypothesis of a fair selector
+    /// at the given significance level (for example 0.01)
+    pub fn is_fair(&self, significance: f64) -> bool {
+        self.chi_squared_p_value >= significance && self.g_p_value >= significance
+    }
+}
+
+impl<T, W: Weight> RandomSelector<T, W> {
+    /// Draw values with the generator of your choice and test they follow
+    /// the probabilities of the choices and of None.
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use rand_select::RandomSelector;
+    /// let selector = RandomSelector::default()
+    ///     .with(1.0, 'A')
+    ///     .with(2.0, 'B')
+    ///     .with_none(1.0);
+    /// let report = selector.test_fairness(10_000, ChaCha8Rng::seed_from_u64(42));
+    /// assert_eq!(report.degrees_of_freedom, 2);
+    /// assert!(report.is_fair(0.001));
+    /// ```
+    pub fn test_fairness<R: Rng>(&self, samples: usize, r: R) -> FairnessReport {
+        let histogram = self.simulate(samples, r);
+        let (counts, probabilities): (Vec<usize>, Vec<f64>) = histogram
+            .bins()
+            .map(|bin| (bin.count, bin.expected))
+            .unzip();
+        FairnessReport::from_counts(&counts, &probabilities)
+    }
+}
+
+/// Return the probability for a chi-squared distributed variable with the
+/// given degrees of freedom to be at least `x`
+fn chi_squared_p_value(x: f64, degrees_of_freedom: usize) -> f64 {
+    if degrees_of_freedom == 0 {
+        return if x > 0.0 { 0.0 } else { 1.0 };
+    }
+    upper_regularized_gamma(degrees_of_freedom as f64 / 2.0, x / 2.0)
+}
+
+const EPSILON: f64 = 1e-15;
+const MAX_ITERATIONS: usize = 10_000;
+
+/// Compute Q(a, x), the regularized upper incomplete gamma function,
+/// with a series when x is small and a continued fraction otherwise
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    if x.is_infinite() {
+        return 0.0;
+    }
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefactor = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..MAX_ITERATIONS {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefactor).max(0.0)
+    } else {
+        // modified Lentz's method
+        let tiny = f64::MIN_POSITIVE / EPSILON;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_ITERATIONS {
+            let i = i as f64;
+            let an = -i * (i - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (h * prefactor).min(1.0)
+    }
+}
+
+/// Compute the logarithm of the gamma function with the Lanczos
+/// approximation (g = 7), valid for `x >= 0.5`
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    let x = x - 1.0;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(value: f64, expected: f64, tolerance: f64) {
+        assert!((value - expected).abs() < tolerance, "{value} vs {expected}");
+    }
+
+    #[test]
+    fn ln_gamma_has_known_values() {
+        assert_close(ln_gamma(1.0), 0.0, 1e-13);
+        assert_close(ln_gamma(2.0), 0.0, 1e-13);
+        assert_close(ln_gamma(5.0), 24f64.ln(), 1e-13);
+        assert_close(ln_gamma(0.5), 0.5 * std::f64::consts::PI.ln(), 1e-13);
+        assert_close(ln_gamma(50.0), 144.565_743_946_344_9, 1e-10);
+    }
+
+    #[test]
+    fn p_values_match_the_critical_values() {
+        // these are computed with the continued fraction
+        assert_close(chi_squared_p_value(3.841, 1), 0.05, 1e-4);
+        assert_close(chi_squared_p_value(5.991, 2), 0.05, 1e-4);
+        assert_close(chi_squared_p_value(124.342, 100), 0.05, 1e-4);
+        // these are computed with the series
+        assert_close(chi_squared_p_value(3.940, 10), 0.95, 1e-4);
+        assert_close(chi_squared_p_value(0.711, 4), 0.95, 1e-4);
+    }
+
+    #[test]
+    fn both_branches_match_the_exact_p_values() {
+        // with 2 degrees of freedom, the p-value is exactly exp(-x/2)
+        for x in [0.01, 1.0, 3.9, 4.1, 10.0, 60.0] {
+            assert_close(chi_squared_p_value(x, 2), (-x / 2.0).exp(), 1e-13);
+        }
+        assert_eq!(chi_squared_p_value(0.0, 3), 1.0);
+        assert_eq!(chi_squared_p_value(f64::INFINITY, 3), 0.0);
+    }
+
+    #[test]
+    fn observed_impossible_outcome_is_unfair() {
+        let report = FairnessReport::from_counts(&[50, 49, 1], &[0.5, 0.5, 0.0]);
+        assert_eq!(report.degrees_of_freedom, 1);
+        assert_eq!(report.chi_squared, f64::INFINITY);
+        assert_eq!(report.g, f64::INFINITY);
+        assert_eq!(report.chi_squared_p_value, 0.0);
+        assert_eq!(report.g_p_value, 0.0);
+        assert!(!report.is_fair(1e-9));
+        let report = FairnessReport::from_counts(&[50, 50, 0], &[0.5, 0.5, 0.0]);
+        assert_eq!(report.degrees_of_freedom, 1);
+        assert_eq!(report.chi_squared, 0.0);
+        assert!(report.is_fair(0.05));
+    }
+}
@@ -0,0 +1,248 @@
+use {
+    crate::*,
+    rand::Rng,
+    std::{
+        fmt,
+        num::{
+            NonZeroU8,
+            NonZeroU16,
+            NonZeroU32,
+            NonZeroU64,
+            NonZeroUsize,
+        },
+        ops::{
+            Add,
+            Sub,
+        },
+    },
+};
+
+/// Tolerance on the comparison of a float total weight with the sum of the
+/// weights, so that rounding errors in the sum don't make it exceed a total
+/// which was intended to be exactly the sum
+const ROUNDING_TOLERANCE: f64 = 1e-9;
+
+/// The type of the weight of a choice.
+///
+/// It's implemented for `f32`, `f64`, `u8`, `u16`, `u32`, `u64`, `usize`,
+/// and their `NonZero` counterparts.
+///
+/// Integer weights are summed and drawn with exact integer arithmetic, so
+/// a weight of 1 out of a total of 3 is exactly a 1/3 chance. They're summed
+/// in a wider type (`u64` or `u128`) so that sums of weights don't overflow,
+/// but a big weight of None may make the total overflow: it then saturates,
+/// or the `try_` methods return [SelectorError::WeightOverflow].
+///
+/// ```
+/// use std::num::NonZeroU16;
+/// use rand_select::RandomSelector;
+/// let selector = RandomSelector::default()
+///    .with(NonZeroU16::new(1).unwrap(), 'A')
+///    .with(NonZeroU16::new(3).unwrap(), 'B')
+///    .with_none(4);
+/// assert_eq!(selector.probability_of(1), Some(0.375));
+///
+/// let selector = RandomSelector::default()
+///    .with(0.5f32, 'A')
+///    .with(1.5f32, 'B');
+/// assert_eq!(selector.probability_of(0), Some(0.25));
+/// ```
+pub trait Weight: Copy + PartialOrd + fmt::Debug {
+    /// The type in which weights are summed
+    type Total: Total;
+    /// The weight of one element, used for uniform selectors
+    const ONE: Self;
+    fn to_total(self) -> Self::Total;
+    /// Make the weight usable without check (a negative float weight
+    /// is taken as its absolute value)
+    fn abs(self) -> Self;
+    /// Check the weight is a finite non negative number
+    fn check(self) -> Result<Self, SelectorError>;
+}
+
+/// A sum of weights, and the type of the total weight of a selector
+pub trait Total: Copy + PartialOrd + fmt::Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    fn to_f64(self) -> f64;
+    /// Make the total usable without check (a negative float total
+    /// is taken as its absolute value)
+    fn abs(self) -> Self;
+    /// Check the total is a finite non negative number
+    fn check(self) -> Result<Self, SelectorError>;
+    /// Return `self - other`, or zero if `other` is greater
+    fn sub_or_zero(self, other: Self) -> Self;
+    /// Return `self + other`, or None if it overflows (is infinite for floats)
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// Return `self + other`, saturating at the maximum for integers
+    fn saturating_add(self, other: Self) -> Self;
+    /// Tell whether this total is smaller than the given sum of weights,
+    /// tolerating rounding errors in the sum
+    fn is_below_sum(self, weights_sum: Self) -> bool;
+    /// Multiply by a count of elements, or return None if it overflows
+    /// (is infinite for floats)
+    fn checked_mul_count(self, count: usize) -> Option<Self>;
+    /// Draw a value uniformly in `[0, self)`
+    fn random_below<R: Rng + ?Sized>(self, rng: &mut R) -> Self;
+}
+
+fn check_float(weight: f64) -> Result<(), SelectorError> {
+    if weight.is_nan() {
+        Err(SelectorError::NanWeight)
+    } else if weight.is_infinite() {
+        Err(SelectorError::InfiniteWeight)
+    } else if weight < 0.0 {
+        Err(SelectorError::NegativeWeight(weight))
+    } else {
+        Ok(())
+    }
+}
+
+impl Weight for f64 {
+    type Total = f64;
+    const ONE: Self = 1.0;
+    fn to_total(self) -> f64 {
+        self
+    }
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+    fn check(self) -> Result<Self, SelectorError> {
+        check_float(self).map(|_| self)
+    }
+}
+
+impl Weight for f32 {
+    type Total = f64;
+    const ONE: Self = 1.0;
+    fn to_total(self) -> f64 {
+        self.into()
+    }
+    fn abs(self) -> Self {
+        f32::abs(self)
+    }
+    fn check(self) -> Result<Self, SelectorError> {
+        check_float(self.into()).map(|_| self)
+    }
+}
+
+impl Total for f64 {
+    const ZERO: Self = 0.0;
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+    fn check(self) -> Result<Self, SelectorError> {
+        check_float(self).map(|_| self)
+    }
+    fn sub_or_zero(self, other: Self) -> Self {
+        (self - other).max(0.0)
+    }
+    fn checked_add(self, other: Self) -> Option<Self> {
+        let sum = self + other;
+        sum.is_finite().then_some(sum)
+    }
+    fn saturating_add(self, other: Self) -> Self {
+        self + other
+    }
+    fn is_below_sum(self, weights_sum: Self) -> bool {
+        self < weights_sum * (1.0 - ROUNDING_TOLERANCE)
+    }
+    fn checked_mul_count(self, count: usize) -> Option<Self> {
+        let product = self * count as f64;
+        product.is_finite().then_some(product)
+    }
+    fn random_below<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+        rng.random_range(0.0..self)
+    }
+}
+
+macro_rules! impl_integer_total {
+    ($($t:ty),*) => {
+        $(
+            impl Total for $t {
+                const ZERO: Self = 0;
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn abs(self) -> Self {
+                    self
+                }
+                fn check(self) -> Result<Self, SelectorError> {
+                    Ok(self)
+                }
+                fn sub_or_zero(self, other: Self) -> Self {
+                    <$t>::saturating_sub(self, other)
+                }
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+                fn saturating_add(self, other: Self) -> Self {
+                    <$t>::saturating_add(self, other)
+                }
+                fn is_below_sum(self, weights_sum: Self) -> bool {
+                    self < weights_sum
+                }
+                fn checked_mul_count(self, count: usize) -> Option<Self> {
+                    <$t>::checked_mul(self, count as $t)
+                }
+                fn random_below<R: Rng + ?Sized>(self, rng: &mut R) -> Self {
+                    rng.random_range(0..self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_total!(u64, u128);
+
+/// Implement Weight for unsigned integers and their NonZero counterparts,
+/// summed in a wider type so that sums don't overflow
+macro_rules! impl_integer_weight {
+    ($($t:ty, $nz:ty => $total:ty),*) => {
+        $(
+            impl Weight for $t {
+                type Total = $total;
+                const ONE: Self = 1;
+                fn to_total(self) -> $total {
+                    self as $total
+                }
+                fn abs(self) -> Self {
+                    self
+                }
+                fn check(self) -> Result<Self, SelectorError> {
+                    Ok(self)
+                }
+            }
+            impl Weight for $nz {
+                type Total = $total;
+                const ONE: Self = <$nz>::MIN;
+                fn to_total(self) -> $total {
+                    self.get() as $total
+                }
+                fn abs(self) -> Self {
+                    self
+                }
+                fn check(self) -> Result<Self, SelectorError> {
+                    Ok(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_weight!(
+    u8, NonZeroU8 => u64,
+    u16, NonZeroU16 => u64,
+    u32, NonZeroU32 => u64,
+    u64, NonZeroU64 => u128,
+    usize, NonZeroUsize => u128
+);
+
+/// Sum the weights of the choices, saturating at the maximum for integers
+pub(crate) fn weights_sum<T, W: Weight>(choices: &[Choice<T, W>]) -> W::Total {
+    choices
+        .iter()
+        .fold(W::Total::ZERO, |sum, choice| sum.saturating_add(choice.weight.to_total()))
+}